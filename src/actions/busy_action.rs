@@ -0,0 +1,35 @@
+use specs::{Entity, WorldExt};
+
+use crate::components::*;
+
+use super::{Action, MoveAction};
+
+/// Spends a turn the actor is busy for, finishing any vault in progress.
+pub struct BusyAction {}
+
+impl Action for BusyAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        let done = {
+            let mut storage = ecs.write_storage::<Busy>();
+            match storage.get_mut(actor) {
+                None => return,
+                Some(busy) => {
+                    busy.turns -= 1;
+                    busy.turns < 1
+                }
+            }
+        };
+        if !done {
+            return;
+        }
+
+        ecs.write_storage::<Busy>().remove(actor);
+        let vaulting = ecs.write_storage::<Vaulting>().remove(actor);
+        if let Some(vaulting) = vaulting {
+            let action = MoveAction {
+                target: vaulting.destination,
+            };
+            action.run(actor, ecs);
+        }
+    }
+}
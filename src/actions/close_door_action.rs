@@ -0,0 +1,56 @@
+use bracket_lib::prelude::Point;
+use specs::{Entity, WorldExt};
+
+use crate::{
+    components::*,
+    game_map::{DoorState, GameMap},
+    gamelog::GameLog,
+    map::Map,
+};
+
+use super::{face_towards, has_component, invalidate_viewsheds, Action};
+
+pub struct CloseDoorAction {
+    pub target: Point,
+}
+
+impl Action for CloseDoorAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        face_towards(ecs, actor, self.target);
+        let is_player = has_component::<Player>(ecs, actor);
+        let mut log = ecs.write_resource::<GameLog>();
+        let mut map = ecs.write_resource::<GameMap>();
+
+        let idx = map.xy_idx(self.target);
+        match map.door_state(idx) {
+            Some(DoorState::Open) => {
+                if !map.entities_tiles[idx].is_empty() {
+                    if is_player {
+                        log.log("Something is in the way.".to_string());
+                    }
+                    return;
+                }
+                map.set_door_state(idx, DoorState::Closed);
+                map.blocked_tiles[idx] = true;
+                if is_player {
+                    log.log("You close the door.".to_string());
+                }
+            }
+            Some(DoorState::Broken) => {
+                if is_player {
+                    log.log("The door is broken.".to_string());
+                }
+                return;
+            }
+            Some(_) => return,
+            None => {
+                if is_player {
+                    log.log("There is no door there.".to_string());
+                }
+                return;
+            }
+        }
+
+        invalidate_viewsheds(ecs);
+    }
+}
@@ -20,19 +20,34 @@ impl Action for EquipItemAction {
         let can_equip = storage.get(self.target).unwrap();
         let target_slot = can_equip.slot;
 
-        // Remove any items the target has in the item's slot
         let mut storage = ecs.write_component::<Equipment>();
         let equipment = storage.get_mut(actor).unwrap();
-        let to_unequip = equipment.slots.get(&target_slot).cloned();
 
-        if let Some(to_unequip) = to_unequip {
-            if is_player {
-                let storage = ecs.read_component::<Name>();
-                let name = storage.get(to_unequip).unwrap();
-                log.log(format!("You unequip {}.", name.name));
+        // Two-handed weapons and shields can't be held together
+        let two_handed = ecs.read_storage::<TwoHanded>();
+        let mut cleared_slots = vec![target_slot];
+        if target_slot == EquipmentSlot::Melee && two_handed.contains(self.target) {
+            cleared_slots.push(EquipmentSlot::Shield);
+        }
+        if target_slot == EquipmentSlot::Shield
+            && equipment
+                .slots
+                .get(&EquipmentSlot::Melee)
+                .is_some_and(|weapon| two_handed.contains(*weapon))
+        {
+            cleared_slots.push(EquipmentSlot::Melee);
+        }
+
+        // Remove any items the target has in the cleared slots
+        for slot in cleared_slots {
+            if let Some(to_unequip) = equipment.slots.remove(&slot) {
+                if is_player {
+                    let storage = ecs.read_component::<Name>();
+                    let name = storage.get(to_unequip).unwrap();
+                    log.log(format!("You unequip {}.", name.name));
+                }
+                inventory.items.push(to_unequip);
             }
-            equipment.slots.remove(&target_slot);
-            inventory.items.push(to_unequip);
         }
 
         equipment.slots.insert(target_slot, self.target);
@@ -0,0 +1,160 @@
+use bracket_lib::prelude::*;
+use specs::{shrev::EventChannel, Entity, World, WorldExt};
+
+use crate::{
+    combat::{roll_damage, total_armor, CRITICAL_BLEEDING_TURNS, SUPPRESSED_ACCURACY_PENALTY},
+    components::*,
+    events::GameEvent,
+    gamelog::GameLog,
+    projectile::{trace_shot, Impact},
+    resources::DebugOptions,
+    status::{has_status, StatusKind},
+    systems::{add_effect, EffectType, ParticleBuilder, Targets},
+};
+
+use super::{face_towards, Action};
+
+const GUNSHOT_VOLUME: i32 = 15;
+/// Turns creatures shot at keep their heads down for
+const SUPPRESSION_TURNS: i32 = 2;
+
+pub struct FireAction {
+    pub target: Point,
+}
+
+/// The firearm in the actor's ranged weapon slot, if any
+pub fn equipped_firearm(ecs: &World, actor: Entity) -> Option<Entity> {
+    let equipments = ecs.read_storage::<Equipment>();
+    let firearms = ecs.read_storage::<Firearm>();
+    let weapon = *equipments.get(actor)?.slots.get(&EquipmentSlot::Ranged)?;
+    firearms.contains(weapon).then_some(weapon)
+}
+
+impl Action for FireAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        let weapon = match equipped_firearm(ecs, actor) {
+            None => return,
+            Some(weapon) => weapon,
+        };
+        face_towards(ecs, actor, self.target);
+
+        let firearm = {
+            let mut log = ecs.write_resource::<GameLog>();
+            let mut events = ecs.write_resource::<EventChannel<GameEvent>>();
+            let names = ecs.read_storage::<Name>();
+            let positions = ecs.read_storage::<Position>();
+            let mut firearms = ecs.write_storage::<Firearm>();
+            let firearm = firearms.get_mut(weapon).unwrap();
+            if firearm.ammo <= 0 {
+                log.log(format!(
+                    "Click. {}'s {} is empty.",
+                    names.get(actor).unwrap().name,
+                    names.get(weapon).unwrap().name
+                ));
+                return;
+            }
+            firearm.ammo -= 1;
+            events.single_write(GameEvent::NoiseMade {
+                pos: positions.get(actor).unwrap().pos,
+                volume: GUNSHOT_VOLUME,
+            });
+            firearm.clone()
+        };
+
+        let accuracy = if has_status(ecs, actor, StatusKind::Suppressed) {
+            firearm.accuracy - SUPPRESSED_ACCURACY_PENALTY
+        } else {
+            firearm.accuracy
+        };
+        let shot = trace_shot(ecs, actor, self.target, firearm.range, accuracy);
+        let damage = match shot.impact {
+            Impact::Entity(victim) => {
+                let armor = {
+                    let combat_stats = ecs.read_storage::<CombatStats>();
+                    total_armor(ecs, victim, combat_stats.get(victim).unwrap())
+                };
+                let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+                Some(roll_damage(&mut rng, firearm.damage, shot.critical, armor))
+            }
+            _ => None,
+        };
+
+        let debug = ecs.read_resource::<DebugOptions>();
+        let mut log = ecs.write_resource::<GameLog>();
+        let mut events = ecs.write_resource::<EventChannel<GameEvent>>();
+        let mut particle_builder = ecs.write_resource::<ParticleBuilder>();
+        let names = ecs.read_storage::<Name>();
+        let positions = ecs.read_storage::<Position>();
+        let mut combat_stats = ecs.write_storage::<CombatStats>();
+        let name = &names.get(actor).unwrap().name;
+
+        for missed in shot.missed.iter() {
+            log.log(format!(
+                "{} misses {}.",
+                name,
+                names.get(*missed).unwrap().name
+            ));
+        }
+
+        match (shot.impact, damage) {
+            (Impact::Entity(victim), Some(damage)) => {
+                let victim_pos = positions.get(victim).unwrap().pos;
+                combat_stats
+                    .get_mut(victim)
+                    .unwrap()
+                    .deal_damage(damage.dealt);
+                events.single_write(GameEvent::EntityDamaged {
+                    target: victim,
+                    pos: victim_pos,
+                    amount: damage.dealt,
+                    bleeds: true,
+                });
+                log.log(format!(
+                    "{} {} {}, for {} hp.",
+                    name,
+                    if shot.critical {
+                        "critically shoots"
+                    } else {
+                        "shoots"
+                    },
+                    names.get(victim).unwrap().name,
+                    damage.dealt
+                ));
+                if debug.show_rolls {
+                    log.log(format!("[{}]", damage.label(firearm.damage, shot.critical)));
+                }
+            }
+            (Impact::Terrain(pos), _) => {
+                particle_builder.request(
+                    pos,
+                    RGB::named(ORANGE),
+                    RGB::named(BLACK),
+                    to_cp437('*'),
+                    200.0,
+                );
+            }
+            _ => {}
+        }
+
+        // Near misses pin creatures down, critical hits leave them bleeding
+        if !shot.missed.is_empty() {
+            let effect = EffectType::Status {
+                kind: StatusKind::Suppressed,
+                turns: SUPPRESSION_TURNS,
+                potency: 1,
+            };
+            let targets = Targets::Entities {
+                targets: shot.missed,
+            };
+            add_effect(ecs, effect, targets);
+        }
+        if let (Impact::Entity(victim), true) = (shot.impact, shot.critical) {
+            let effect = EffectType::Status {
+                kind: StatusKind::Bleeding,
+                turns: CRITICAL_BLEEDING_TURNS,
+                potency: 1,
+            };
+            add_effect(ecs, effect, Targets::Single { target: victim });
+        }
+    }
+}
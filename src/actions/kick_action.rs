@@ -0,0 +1,58 @@
+use bracket_lib::prelude::{Point, RandomNumberGenerator};
+use specs::{Entity, WorldExt};
+
+use crate::{
+    components::*,
+    events::{publish, GameEvent},
+    game_map::{GameMap, TileType},
+    gamelog::GameLog,
+    map::Map,
+};
+
+use super::{face_towards, has_component, invalidate_viewsheds, Action};
+
+pub struct KickAction {
+    pub target: Point,
+}
+
+impl Action for KickAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        face_towards(ecs, actor, self.target);
+        let is_player = has_component::<Player>(ecs, actor);
+        let mut log = ecs.write_resource::<GameLog>();
+        let mut map = ecs.write_resource::<GameMap>();
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+
+        let idx = map.xy_idx(self.target);
+        let tile = map.tiles[idx];
+        let kickable = map.is_door_closed(idx) || tile == TileType::Window;
+        if !kickable {
+            if is_player {
+                log.log("There is nothing to breach there.".to_string());
+            }
+            return;
+        }
+
+        let destroyed = map.damage_tile(idx, rng.roll_dice(2, 4));
+        publish(
+            ecs,
+            GameEvent::NoiseMade {
+                pos: self.target,
+                volume: if destroyed { 10 } else { 6 },
+            },
+        );
+        if is_player {
+            let message = match (tile, destroyed) {
+                (TileType::Window, true) => "You kick the window in.",
+                (TileType::Window, false) => "The window cracks.",
+                (_, true) => "You kick the door open!",
+                (_, false) => "The door holds.",
+            };
+            log.log(message.to_string());
+        }
+
+        if destroyed {
+            invalidate_viewsheds(ecs);
+        }
+    }
+}
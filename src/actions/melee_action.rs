@@ -2,14 +2,19 @@ use bracket_lib::prelude::*;
 use specs::{Entity, WorldExt};
 
 use crate::{
+    combat::{
+        roll_attack, roll_damage, total_armor, total_dodge, AttackOutcome, CRITICAL_BLEEDING_TURNS,
+        SUPPRESSED_ATTACK_PENALTY,
+    },
     components::*,
-    game_map::{Decal, GameMap},
+    events::{publish, GameEvent},
     gamelog::GameLog,
-    map::Map,
-    systems::ParticleBuilder,
+    resources::DebugOptions,
+    status::{has_status, StatusKind},
+    systems::{add_effect, EffectType, Targets},
 };
 
-use super::{map_equipped_items_comp, Action};
+use super::{face_towards, map_equipped_items_comp, Action};
 
 // struct WorldEntityRef<'a> {
 //     world: &'a World,
@@ -30,69 +35,105 @@ pub struct MeleeAction {
 
 impl Action for MeleeAction {
     fn run(&self, actor: Entity, ecs: &mut specs::World) {
-        let mut particle_builder = ecs.write_resource::<ParticleBuilder>();
-        let mut log = ecs.write_resource::<GameLog>();
-
-        let mut combat_stats_storage = ecs.write_storage::<CombatStats>();
-        let stats = combat_stats_storage.get(actor).unwrap().clone();
-        let target_stats = combat_stats_storage.get_mut(self.target).unwrap();
-
-        let name_storage = ecs.read_storage::<Name>();
-        let name = name_storage.get(actor).unwrap();
-        let target_name = name_storage.get(self.target).unwrap();
-
-        let storage = ecs.read_storage::<Position>();
-        let target_pos = storage.get(self.target);
+        let target_pos = ecs
+            .read_storage::<Position>()
+            .get(self.target)
+            .map(|p| p.pos);
+        if let Some(target_pos) = target_pos {
+            face_towards(ecs, actor, target_pos);
+        }
 
-        if stats.hp > 0 {
-            let mut offensive_bonus = 0;
-            for power_bonus in
-                map_equipped_items_comp::<MeleePowerBonus, i32>(ecs, actor, |mpb| mpb.power)
-            {
-                offensive_bonus += power_bonus;
+        let (stats, target_stats) = {
+            let combat_stats = ecs.read_storage::<CombatStats>();
+            match (combat_stats.get(actor), combat_stats.get(self.target)) {
+                (Some(stats), Some(target_stats)) => (stats.clone(), target_stats.clone()),
+                _ => return,
             }
+        };
+        if stats.hp <= 0 || target_stats.hp <= 0 {
+            return;
+        }
 
-            if target_stats.hp > 0 {
-                let mut defensive_bonus = 0;
-
-                for defense_bonus in
-                    map_equipped_items_comp::<DefenseBonus, i32>(ecs, self.target, |db| db.defense)
-                {
-                    defensive_bonus += defense_bonus;
-                }
-
-                if let Some(pos) = target_pos {
-                    let mut map = ecs.write_resource::<GameMap>();
-                    let idx = map.xy_idx(pos.pos);
-                    map.decal_tiles.insert(idx, Decal::blood());
+        let weapon = map_equipped_items_comp::<MeleeWeapon, MeleeWeapon>(ecs, actor, |w| w.clone())
+            .into_iter()
+            .next();
+        let (mut hit_bonus, dice) = match weapon {
+            Some(weapon) => (stats.attack + weapon.hit_bonus, weapon.damage),
+            None => (stats.attack, stats.damage),
+        };
+        if has_status(ecs, actor, StatusKind::Suppressed) {
+            hit_bonus -= SUPPRESSED_ATTACK_PENALTY;
+        }
+        let dodge = total_dodge(ecs, self.target, &target_stats);
+        let armor = total_armor(ecs, self.target, &target_stats);
 
-                    particle_builder.request(
-                        pos.pos,
-                        RGB::named(ORANGE),
-                        RGB::named(BLACK),
-                        to_cp437('‼'),
-                        200.0,
-                    );
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        let mut log = ecs.write_resource::<GameLog>();
+        let debug = ecs.read_resource::<DebugOptions>();
+        let names = ecs.read_storage::<Name>();
+        let name = &names.get(actor).unwrap().name;
+        let target_name = &names.get(self.target).unwrap().name;
+
+        let attack = roll_attack(&mut rng, hit_bonus, dodge);
+        let critical = match attack.outcome {
+            AttackOutcome::Fumble => {
+                log.log(format!("{} fumbles the attack.", name));
+                if debug.show_rolls {
+                    log.log(format!("[{}]", attack.label()));
                 }
-
-                let damage = i32::max(
-                    0,
-                    stats.power + offensive_bonus - target_stats.defense - defensive_bonus,
-                );
-
-                if damage == 0 {
-                    log.log(format!(
-                        "{} is unable to hurt {}",
-                        &name.name, &target_name.name
-                    ));
-                } else {
-                    log.log(format!(
-                        "{} hits {}, for {} hp.",
-                        &name.name, &target_name.name, damage
-                    ));
-                    target_stats.deal_damage(damage);
+                return;
+            }
+            AttackOutcome::Miss => {
+                log.log(format!("{} misses {}.", name, target_name));
+                if debug.show_rolls {
+                    log.log(format!("[{}]", attack.label()));
                 }
+                return;
             }
+            AttackOutcome::Hit => false,
+            AttackOutcome::Critical => true,
+        };
+
+        let damage = roll_damage(&mut rng, dice, critical, armor);
+        log.log(format!(
+            "{} {} {}, for {} hp.",
+            name,
+            if critical { "critically hits" } else { "hits" },
+            target_name,
+            damage.dealt
+        ));
+        if debug.show_rolls {
+            log.log(format!(
+                "[{}, {}]",
+                attack.label(),
+                damage.label(dice, critical)
+            ));
+        }
+        ecs.write_storage::<CombatStats>()
+            .get_mut(self.target)
+            .unwrap()
+            .deal_damage(damage.dealt);
+        if let Some(pos) = target_pos {
+            publish(
+                ecs,
+                GameEvent::EntityDamaged {
+                    target: self.target,
+                    pos,
+                    amount: damage.dealt,
+                    bleeds: true,
+                },
+            );
+        }
+        if critical {
+            let effect = EffectType::Status {
+                kind: StatusKind::Bleeding,
+                turns: CRITICAL_BLEEDING_TURNS,
+                potency: 1,
+            };
+            let targets = Targets::Single {
+                target: self.target,
+            };
+            add_effect(ecs, effect, targets);
         }
     }
 }
@@ -14,10 +14,29 @@ mod use_item_action;
 pub use use_item_action::*;
 mod wait_action;
 pub use wait_action::*;
+mod open_door_action;
+pub use open_door_action::*;
+mod close_door_action;
+pub use close_door_action::*;
+mod kick_action;
+pub use kick_action::*;
+mod busy_action;
+pub use busy_action::*;
+mod vault_action;
+pub use vault_action::*;
+mod turn_action;
+pub use turn_action::*;
+mod fire_action;
+pub use fire_action::*;
+mod reload_action;
+pub use reload_action::*;
+mod throw_action;
+pub use throw_action::*;
 
-use specs::*;
+use bracket_lib::prelude::Point;
+use specs::{saveload::*, *};
 
-use crate::components::Equipment;
+use crate::components::*;
 
 pub trait Action {
     fn run(&self, actor: Entity, ecs: &mut World);
@@ -49,3 +68,81 @@ pub fn map_equipped_items_comp<T: Component, R>(
         Vec::new()
     }
 }
+
+/// Turns the actor towards a tile
+pub fn face_towards(ecs: &World, actor: Entity, target: Point) {
+    let positions = ecs.read_storage::<Position>();
+    let mut facings = ecs.write_storage::<Facing>();
+    if let (Some(pos), Some(facing)) = (positions.get(actor), facings.get_mut(actor)) {
+        let delta = target - pos.pos;
+        let direction = Point::new(delta.x.signum(), delta.y.signum());
+        if delta != Point::zero() && direction != facing.direction {
+            facing.direction = direction;
+            if let Some(viewshed) = ecs.write_storage::<Viewshed>().get_mut(actor) {
+                viewshed.dirty = true;
+            }
+        }
+    }
+}
+
+/// Forces every viewshed to be recomputed, e.g. after the line of sight was changed by terrain.
+pub fn invalidate_viewsheds(ecs: &World) {
+    let mut viewsheds = ecs.write_storage::<Viewshed>();
+    for viewshed in (&mut viewsheds).join() {
+        viewshed.dirty = true;
+    }
+}
+
+fn copy_component<T: Component + Clone>(ecs: &World, from: Entity, to: Entity) {
+    let mut storage = ecs.write_storage::<T>();
+    if let Some(component) = storage.get(from).cloned() {
+        storage
+            .insert(to, component)
+            .expect("Unable to copy component");
+    }
+}
+
+/// Takes a single item off a stack, returning it as a new entity.
+/// Items that are not stacked are returned as is.
+/// Copies every item component: keep the list in step with `components.rs`.
+pub fn split_stack(ecs: &mut World, item: Entity) -> Entity {
+    {
+        let mut quantities = ecs.write_storage::<Quantity>();
+        match quantities.get_mut(item) {
+            Some(quantity) if quantity.amount > 1 => quantity.amount -= 1,
+            _ => return item,
+        }
+    }
+
+    let split = ecs
+        .create_entity()
+        .with(Quantity { amount: 1 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+    copy_component::<Name>(ecs, item, split);
+    copy_component::<Renderable>(ecs, item, split);
+    copy_component::<Item>(ecs, item, split);
+    copy_component::<Consumable>(ecs, item, split);
+    copy_component::<Weight>(ecs, item, split);
+    copy_component::<ProvidesHealing>(ecs, item, split);
+    copy_component::<Ranged>(ecs, item, split);
+    copy_component::<InflictsDamage>(ecs, item, split);
+    copy_component::<AreaOfEffect>(ecs, item, split);
+    copy_component::<InflictsStatus>(ecs, item, split);
+    copy_component::<SpawnsEntity>(ecs, item, split);
+    copy_component::<Teleports>(ecs, item, split);
+    copy_component::<Identifies>(ecs, item, split);
+    copy_component::<Recharges>(ecs, item, split);
+    copy_component::<Grenade>(ecs, item, split);
+    copy_component::<Shatters>(ecs, item, split);
+    copy_component::<Charges>(ecs, item, split);
+    copy_component::<Equippable>(ecs, item, split);
+    copy_component::<TwoHanded>(ecs, item, split);
+    copy_component::<MeleeWeapon>(ecs, item, split);
+    copy_component::<DefenseBonus>(ecs, item, split);
+    copy_component::<Firearm>(ecs, item, split);
+    copy_component::<Ammunition>(ecs, item, split);
+    copy_component::<LightSource>(ecs, item, split);
+    copy_component::<NightVision>(ecs, item, split);
+    split
+}
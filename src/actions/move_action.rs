@@ -1,9 +1,17 @@
-use bracket_lib::prelude::Point;
-use specs::WorldExt;
+use bracket_lib::prelude::{Point, RandomNumberGenerator};
+use specs::{Entity, World, WorldExt};
 
-use crate::{components::*, game_map::GameMap, map::Map, resources::PlayerPos};
+use crate::{
+    components::*,
+    encumbrance::{encumbrance, Encumbrance},
+    game_map::GameMap,
+    gamelog::GameLog,
+    map::Map,
+    resources::{Movements, PlayerEntity, PlayerPos},
+    status::{has_status, StatusKind},
+};
 
-use super::{has_component, Action};
+use super::{face_towards, has_component, Action};
 
 pub struct MoveAction {
     pub target: Point,
@@ -11,30 +19,74 @@ pub struct MoveAction {
 
 impl Action for MoveAction {
     fn run(&self, actor: specs::Entity, ecs: &mut specs::World) {
+        let target = stumble(ecs, actor, self.target);
+        face_towards(ecs, actor, target);
         let mut map = ecs.write_resource::<GameMap>();
         let mut player_pos = ecs.write_resource::<PlayerPos>();
+        let player_entity = ecs.read_resource::<PlayerEntity>();
 
         let mut storage = ecs.write_storage::<Viewshed>();
         let viewshed = storage.get_mut(actor);
         let mut storage = ecs.write_storage::<Position>();
         let position = storage.get_mut(actor).unwrap();
 
-        let is_player = has_component::<Player>(ecs, actor);
-
-        let target_idx = map.xy_idx(self.target);
+        let target_idx = map.xy_idx(target);
         if !map.blocked_tiles[target_idx] {
             let pos_idx = map.xy_idx(position.pos);
             map.blocked_tiles[pos_idx] = false;
             map.blocked_tiles[target_idx] = true;
-            position.pos = self.target;
+            position.pos = target;
 
-            if is_player {
-                player_pos.pos = self.target;
+            if actor == player_entity.entity {
+                player_pos.pos = target;
             }
 
-            if let Some(mut viewshed) = viewshed {
+            if let Some(viewshed) = viewshed {
                 viewshed.dirty = true;
             }
+
+            if has_component::<Player>(ecs, actor) {
+                ecs.write_resource::<Movements>().record(target);
+            }
+
+            // Rough terrain slows the actor down
+            let terrain_turns = map.extra_move_turns(target_idx);
+            // Heavy loads slow the actor down as well
+            let load_turns = match encumbrance(ecs, actor) {
+                Encumbrance::Unburdened => 0,
+                Encumbrance::Burdened | Encumbrance::Overloaded => 1,
+            };
+            let extra_turns = terrain_turns + load_turns;
+            if extra_turns > 0 {
+                ecs.write_storage::<Busy>()
+                    .insert(actor, Busy { turns: extra_turns })
+                    .expect("Unable to insert busy");
+            }
         }
     }
 }
+
+/// Confused creatures wander off in a random direction half of the time
+fn stumble(ecs: &World, actor: Entity, target: Point) -> Point {
+    if !has_status(ecs, actor, StatusKind::Confused) {
+        return target;
+    }
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+    if rng.roll_dice(1, 2) == 1 {
+        return target;
+    }
+    let pos = ecs.read_storage::<Position>().get(actor).unwrap().pos;
+    let stumbled = pos + Point::new(rng.range(-1, 2), rng.range(-1, 2));
+    // Stumbling into something or off the map leaves them on course
+    if !ecs.read_resource::<GameMap>().is_exit_valid(stumbled) {
+        return target;
+    }
+    if has_component::<Player>(ecs, actor) {
+        let mut log = ecs.write_resource::<GameLog>();
+        log.log(format!(
+            "{} stumbles around in confusion.",
+            ecs.read_storage::<Name>().get(actor).unwrap().name
+        ));
+    }
+    stumbled
+}
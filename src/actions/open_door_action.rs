@@ -0,0 +1,43 @@
+use bracket_lib::prelude::Point;
+use specs::{Entity, WorldExt};
+
+use crate::{
+    components::*,
+    events::{publish, GameEvent},
+    game_map::{DoorState, GameMap},
+    gamelog::GameLog,
+    map::Map,
+};
+
+use super::{face_towards, has_component, invalidate_viewsheds, Action};
+
+pub struct OpenDoorAction {
+    pub target: Point,
+}
+
+impl Action for OpenDoorAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        face_towards(ecs, actor, self.target);
+        let is_player = has_component::<Player>(ecs, actor);
+        let mut log = ecs.write_resource::<GameLog>();
+        let mut map = ecs.write_resource::<GameMap>();
+
+        let idx = map.xy_idx(self.target);
+        match map.door_state(idx) {
+            Some(DoorState::Closed) => {
+                map.set_door_state(idx, DoorState::Open);
+                map.blocked_tiles[idx] = false;
+                publish(ecs, GameEvent::DoorOpened { actor });
+            }
+            Some(DoorState::Locked) => {
+                if is_player {
+                    log.log("The door is locked.".to_string());
+                }
+                return;
+            }
+            _ => return,
+        }
+
+        invalidate_viewsheds(ecs);
+    }
+}
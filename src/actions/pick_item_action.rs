@@ -1,6 +1,12 @@
-use specs::{Entity, WorldExt};
+use specs::{Entity, World, WorldExt};
 
-use crate::{components::*, gamelog::GameLog};
+use crate::{
+    components::*,
+    encumbrance::can_pick_up,
+    events::{publish, GameEvent},
+    gamelog::GameLog,
+    identification::item_name,
+};
 
 use super::{has_component, Action};
 
@@ -10,18 +16,63 @@ pub struct PickItemAction {
 
 impl Action for PickItemAction {
     fn run(&self, actor: Entity, ecs: &mut specs::World) {
-        let mut storage = ecs.write_storage::<Inventory>();
-        let inventory = storage.get_mut(actor).unwrap();
-        inventory.items.push(self.target);
-
-        if has_component::<Player>(ecs, actor) {
-            let mut log = ecs.write_resource::<GameLog>();
-            let storage = ecs.read_component::<Name>();
-            let item_name = storage.get(self.target).unwrap().name.clone();
-            log.log(format!("You pick up the {}.", item_name))
+        if !can_pick_up(ecs, actor, self.target) {
+            if has_component::<Player>(ecs, actor) {
+                let mut log = ecs.write_resource::<GameLog>();
+                log.log(format!(
+                    "You are carrying too much to pick up the {}.",
+                    item_name(ecs, self.target)
+                ));
+            }
+            return;
         }
 
+        // Picking a lit grenade up pinches its fuse out
+        ecs.write_storage::<LitFuse>().remove(self.target);
+
+        let picked = match matching_stack(ecs, actor, self.target) {
+            Some(stack) => {
+                let mut quantities = ecs.write_storage::<Quantity>();
+                let amount = quantities.get(self.target).unwrap().amount;
+                quantities.get_mut(stack).unwrap().amount += amount;
+
+                let entities = ecs.entities();
+                entities.delete(self.target).expect("Delete failed");
+                stack
+            }
+            None => {
+                let mut storage = ecs.write_storage::<Inventory>();
+                let inventory = storage.get_mut(actor).unwrap();
+                inventory.items.push(self.target);
+                self.target
+            }
+        };
+
+        publish(
+            ecs,
+            GameEvent::ItemPickedUp {
+                actor,
+                item: picked,
+            },
+        );
+
         let mut storage = ecs.write_component::<Position>();
         storage.remove(self.target);
     }
 }
+
+/// A stack of the same item already carried by the actor
+fn matching_stack(ecs: &World, actor: Entity, item: Entity) -> Option<Entity> {
+    let quantities = ecs.read_storage::<Quantity>();
+    let names = ecs.read_storage::<Name>();
+    let inventories = ecs.read_storage::<Inventory>();
+    if !quantities.contains(item) {
+        return None;
+    }
+    let name = &names.get(item)?.name;
+    inventories.get(actor)?.items.iter().copied().find(|other| {
+        *other != item
+            && quantities.contains(*other)
+            && names.get(*other).is_some_and(|n| n.name == *name)
+    })
+}
@@ -0,0 +1,82 @@
+use specs::{Entity, World, WorldExt};
+
+use crate::{components::*, gamelog::GameLog};
+
+use super::{equipped_firearm, has_component, Action};
+
+pub struct ReloadAction {}
+
+pub fn is_magazine_full(ecs: &World, weapon: Entity) -> bool {
+    let firearms = ecs.read_storage::<Firearm>();
+    let firearm = firearms.get(weapon).unwrap();
+    firearm.ammo >= firearm.magazine
+}
+
+/// Whether the actor carries rounds of the weapon's caliber
+pub fn has_ammo_for(ecs: &World, actor: Entity, weapon: Entity) -> bool {
+    let firearms = ecs.read_storage::<Firearm>();
+    let ammunitions = ecs.read_storage::<Ammunition>();
+    let inventories = ecs.read_storage::<Inventory>();
+    let caliber = firearms.get(weapon).unwrap().caliber;
+    inventories.get(actor).is_some_and(|inventory| {
+        inventory.items.iter().any(|item| {
+            ammunitions
+                .get(*item)
+                .is_some_and(|ammunition| ammunition.caliber == caliber && ammunition.rounds > 0)
+        })
+    })
+}
+
+impl Action for ReloadAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        let is_player = has_component::<Player>(ecs, actor);
+        let weapon = match equipped_firearm(ecs, actor) {
+            None => return,
+            Some(weapon) => weapon,
+        };
+        if is_magazine_full(ecs, weapon) || !has_ammo_for(ecs, actor, weapon) {
+            return;
+        }
+        let mut log = ecs.write_resource::<GameLog>();
+
+        let mut firearms = ecs.write_storage::<Firearm>();
+        let firearm = firearms.get_mut(weapon).unwrap();
+        let mut ammunitions = ecs.write_storage::<Ammunition>();
+        let mut inventories = ecs.write_storage::<Inventory>();
+        let inventory = inventories.get_mut(actor).unwrap();
+
+        // Take rounds from the inventory until the magazine is full
+        let mut emptied = Vec::new();
+        for item in inventory.items.iter() {
+            if firearm.ammo >= firearm.magazine {
+                break;
+            }
+            if let Some(ammunition) = ammunitions.get_mut(*item) {
+                if ammunition.caliber != firearm.caliber {
+                    continue;
+                }
+                let rounds = i32::min(ammunition.rounds, firearm.magazine - firearm.ammo);
+                ammunition.rounds -= rounds;
+                firearm.ammo += rounds;
+                if ammunition.rounds <= 0 {
+                    emptied.push(*item);
+                }
+            }
+        }
+
+        inventory.items.retain(|item| !emptied.contains(item));
+        let entities = ecs.entities();
+        for item in emptied {
+            entities.delete(item).expect("Delete failed");
+        }
+
+        if is_player {
+            let names = ecs.read_storage::<Name>();
+            let weapon_name = &names.get(weapon).unwrap().name;
+            log.log(format!(
+                "You reload the {} ({}/{}).",
+                weapon_name, firearm.ammo, firearm.magazine
+            ));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use bracket_lib::prelude::*;
+use specs::{Entity, World, WorldExt};
+
+use crate::{
+    components::*,
+    events::{publish, GameEvent},
+    game_map::GameMap,
+    gamelog::GameLog,
+    identification::{identify, item_name},
+    map::Map,
+    projectile::blocks_projectile,
+    systems::add_effect,
+};
+
+use super::{area_targets, face_towards, has_component, item_effects, split_stack, Action};
+
+pub const THROW_RANGE: i32 = 6;
+
+pub struct ThrowAction {
+    pub item: Entity,
+    pub target: Point,
+}
+
+impl Action for ThrowAction {
+    fn run(&self, actor: Entity, ecs: &mut World) {
+        // Only one item of a stack is thrown at a time
+        let thrown = split_stack(ecs, self.item);
+        if thrown == self.item {
+            let mut storage = ecs.write_storage::<Inventory>();
+            let inventory = storage.get_mut(actor).unwrap();
+            if let Some(index) = inventory.items.iter().position(|ent| *ent == self.item) {
+                inventory.items.remove(index);
+            }
+        }
+
+        face_towards(ecs, actor, self.target);
+        let landing = flight_path(ecs, actor, self.target);
+
+        let name = item_name(ecs, thrown);
+        if has_component::<Player>(ecs, actor) {
+            ecs.write_resource::<GameLog>()
+                .log(format!("You throw the {}.", name));
+        }
+        publish(
+            ecs,
+            GameEvent::NoiseMade {
+                pos: landing,
+                volume: 5,
+            },
+        );
+
+        if let Some(fuse) = ecs.read_storage::<Grenade>().get(thrown).map(|g| g.fuse) {
+            ecs.write_storage::<LitFuse>()
+                .insert(thrown, LitFuse { turns: fuse })
+                .expect("Unable to light fuse");
+        } else if has_component::<Shatters>(ecs, thrown) {
+            let targets = {
+                let map = ecs.read_resource::<GameMap>();
+                if map.visible_tiles[map.xy_idx(landing)] {
+                    ecs.write_resource::<GameLog>()
+                        .log(format!("The {} shatters.", name));
+                }
+                area_targets(&map, landing, 0)
+            };
+            if has_component::<Player>(ecs, actor) {
+                identify(ecs, thrown);
+            }
+            for (effect, targets) in item_effects(ecs, thrown, actor, Some(landing), targets) {
+                add_effect(ecs, effect, targets);
+            }
+            ecs.entities().delete(thrown).expect("Delete failed");
+            return;
+        }
+
+        ecs.write_storage::<Position>()
+            .insert(thrown, Position { pos: landing })
+            .expect("Unable to insert position");
+    }
+}
+
+/// Where the thrown item comes down, short of the first wall or closed door on its way.
+/// Windows in the way are smashed.
+fn flight_path(ecs: &World, actor: Entity, target: Point) -> Point {
+    let mut map = ecs.write_resource::<GameMap>();
+    let origin = ecs.read_storage::<Position>().get(actor).unwrap().pos;
+
+    let mut landing = origin;
+    for tile in line2d(LineAlg::Bresenham, origin, target)
+        .into_iter()
+        .skip(1)
+        .take(THROW_RANGE as usize)
+    {
+        let idx = map.xy_idx(tile);
+        if map.break_window(idx) {
+            ecs.write_resource::<GameLog>()
+                .log("The window shatters!".to_string());
+            publish(
+                ecs,
+                GameEvent::NoiseMade {
+                    pos: tile,
+                    volume: 10,
+                },
+            );
+        }
+        if blocks_projectile(&map, idx) {
+            break;
+        }
+        landing = tile;
+    }
+    landing
+}
@@ -0,0 +1,14 @@
+use bracket_lib::prelude::Point;
+use specs::Entity;
+
+use super::{face_towards, Action};
+
+pub struct TurnAction {
+    pub target: Point,
+}
+
+impl Action for TurnAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        face_towards(ecs, actor, self.target);
+    }
+}
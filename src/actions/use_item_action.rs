@@ -1,12 +1,15 @@
 use bracket_lib::prelude::*;
-use specs::{Entity, WorldExt};
+use specs::{Entity, World, WorldExt};
 
 use crate::{
     components::*,
-    game_map::{Decal, GameMap},
+    events::{publish, GameEvent},
+    game_map::GameMap,
     gamelog::GameLog,
+    identification::{identify, item_name},
     map::Map,
-    systems::ParticleBuilder,
+    projectile::{blocks_projectile, trace_shot, Impact},
+    systems::{add_effect, EffectType, Targets},
 };
 
 use super::{has_component, Action};
@@ -18,170 +21,79 @@ pub struct UseItemAction {
 
 impl Action for UseItemAction {
     fn run(&self, actor: Entity, ecs: &mut specs::World) {
-        let mut used_item: bool = false;
-        let mut map = ecs.write_resource::<GameMap>();
-        let mut particle_builder = ecs.write_resource::<ParticleBuilder>();
-        let is_player = has_component::<Player>(ecs, actor);
-        let mut log = ecs.write_resource::<GameLog>();
+        if let Some(reason) = use_refusal(ecs, actor, self.item) {
+            if has_component::<Player>(ecs, actor) {
+                ecs.write_resource::<GameLog>().log(reason);
+            }
+            return;
+        }
+        let charges = ecs
+            .read_storage::<Charges>()
+            .get(self.item)
+            .map(|device| device.charges);
 
-        // Targeting
-        let mut targets: Vec<Entity> = Vec::new();
-        match self.target {
-            None => {
-                targets.push(actor);
+        // Single target items fly towards their target
+        let shot = match self.target {
+            Some(target) if !has_component::<AreaOfEffect>(ecs, self.item) => {
+                let range = ecs
+                    .read_storage::<Ranged>()
+                    .get(self.item)
+                    .map_or(1, |ranged| ranged.range);
+                Some(trace_shot(ecs, actor, target, range, 100))
             }
+            _ => None,
+        };
+
+        // Targeting
+        let targets = match self.target {
+            None => Some(Targets::Single { target: actor }),
             Some(target) => {
                 let storage = ecs.read_component::<AreaOfEffect>();
-                let area_effect = storage.get(self.item);
-                match area_effect {
-                    None => {
-                        // Single target in tile
-                        let idx = map.xy_idx(target);
-                        for mob in map.entities_tiles[idx].iter() {
-                            targets.push(*mob);
-                        }
-                    }
+                match storage.get(self.item) {
+                    None => match shot.map(|shot| shot.impact) {
+                        Some(Impact::Entity(victim)) => Some(Targets::Single { target: victim }),
+                        // Wasted on a wall or on thin air
+                        _ => None,
+                    },
                     Some(area_effect) => {
-                        // AoE
-                        let mut blast_tiles = field_of_view(target, area_effect.radius, &*map);
-                        blast_tiles.retain(|p| {
-                            p.x > 0 && p.x < map.width - 1 && p.y > 0 && p.y < map.height - 1
-                        });
-                        for tile_idx in blast_tiles.iter() {
-                            let idx = map.xy_idx(*tile_idx);
-                            for mob in map.entities_tiles[idx].iter() {
-                                targets.push(*mob);
-                            }
-                            particle_builder.request(
-                                *tile_idx,
-                                RGB::named(ORANGE),
-                                RGB::named(BLACK),
-                                to_cp437('░'),
-                                200.0,
-                            );
-                        }
+                        let map = ecs.read_resource::<GameMap>();
+                        Some(area_targets(&map, target, area_effect.radius))
                     }
                 }
             }
-        }
+        };
 
-        let mut combat_stats_storage = ecs.write_component::<CombatStats>();
-        let name_storage = ecs.write_component::<Name>();
-        let position_storage = ecs.write_component::<Position>();
-
-        let storage = ecs.read_component::<ProvidesHealing>();
-        let item_heals = storage.get(self.item);
-        match item_heals {
-            None => {}
-            Some(healer) => {
-                for target in targets.iter() {
-                    let target_stats = combat_stats_storage.get_mut(*target);
-                    if let Some(target_stats) = target_stats {
-                        target_stats.heal(healer.heal_amount);
-                        if is_player {
-                            log.log(format!(
-                                "You use the {}, healing {} hp.",
-                                name_storage.get(self.item).unwrap().name,
-                                healer.heal_amount
-                            ));
-                        }
-                        used_item = true;
-                    }
+        // Using an item shows what it really is
+        if has_component::<Player>(ecs, actor) {
+            let name = item_name(ecs, self.item);
+            ecs.write_resource::<GameLog>()
+                .log(format!("You use the {}.", name));
+            identify(ecs, self.item);
+        }
 
-                    let pos = position_storage.get(*target);
-                    if let Some(pos) = pos {
-                        particle_builder.request(
-                            pos.pos,
-                            RGB::named(GREEN),
-                            RGB::named(BLACK),
-                            to_cp437('♥'),
-                            200.0,
-                        );
-                    }
-                }
+        if let Some(targets) = targets {
+            for (effect, targets) in item_effects(ecs, self.item, actor, self.target, targets) {
+                add_effect(ecs, effect, targets);
             }
         }
 
-        let storage = ecs.read_component::<InflictsDamage>();
-        let item_damages = storage.get(self.item);
-        match item_damages {
-            None => {}
-            Some(damage) => {
-                for target in targets.iter() {
-                    let target_stats = combat_stats_storage.get_mut(*target);
-                    if let Some(target_stats) = target_stats {
-                        let target_position = position_storage.get(*target).unwrap().pos;
-                        let idx = map.xy_idx(target_position);
-                        map.decal_tiles.insert(idx, Decal::blood());
-                        target_stats.deal_damage(damage.damage);
-                    }
-                    if is_player {
-                        let mob_name = name_storage.get(*target).unwrap();
-                        let item_name = name_storage.get(self.item).unwrap();
-                        log.log(format!(
-                            "You use {} on {}, inflicting {} hp.",
-                            item_name.name, mob_name.name, damage.damage
-                        ));
-
-                        let pos = position_storage.get(*target);
-                        if let Some(pos) = pos {
-                            particle_builder.request(
-                                pos.pos,
-                                RGB::named(RED),
-                                RGB::named(BLACK),
-                                to_cp437('‼'),
-                                200.0,
-                            );
-                        }
-                    }
-                    used_item = true;
-                }
-            }
+        if let Some(charges) = ecs.write_storage::<Charges>().get_mut(self.item) {
+            charges.charges -= 1;
         }
 
-        // Can it pass along confusion? Note the use of scopes to escape from the borrow checker!
-        let mut add_confusion = Vec::new();
-        {
-            let storage = ecs.read_component::<Confusion>();
-            let causes_confusion = storage.get(self.item);
-            match causes_confusion {
-                None => {}
-                Some(confusion) => {
-                    used_item = false;
-                    for mob in targets.iter() {
-                        add_confusion.push((*mob, confusion.turns));
-                        if is_player {
-                            let mob_name = name_storage.get(*mob).unwrap();
-                            let item_name = name_storage.get(self.item).unwrap();
-                            log.log(format!(
-                                "You use {} on {}, confusing them.",
-                                item_name.name, mob_name.name
-                            ));
-
-                            let pos = position_storage.get(*mob);
-                            if let Some(pos) = pos {
-                                particle_builder.request(
-                                    pos.pos,
-                                    RGB::named(MAGENTA),
-                                    RGB::named(BLACK),
-                                    to_cp437('?'),
-                                    200.0,
-                                );
-                            }
-                        }
-                    }
+        if has_component::<Consumable>(ecs, self.item) {
+            // Charged items are used up with their last charge
+            if charges.is_some_and(|charges| charges > 1) {
+                return;
+            }
+            // Stacked items are used up one at a time
+            if let Some(quantity) = ecs.write_storage::<Quantity>().get_mut(self.item) {
+                if quantity.amount > 1 {
+                    quantity.amount -= 1;
+                    return;
                 }
             }
-        }
-
-        let mut confused_storage = ecs.write_component::<Confused>();
-        for mob in add_confusion.iter() {
-            confused_storage
-                .insert(mob.0, Confused { turns: mob.1 })
-                .expect("Unable to insert status");
-        }
 
-        if has_component::<Consumable>(ecs, self.item) && used_item {
             let mut storage = ecs.write_storage::<Inventory>();
             let inventory = storage.get_mut(actor).unwrap();
             if let Some(index) = inventory.items.iter().position(|ent| *ent == self.item) {
@@ -193,3 +105,142 @@ impl Action for UseItemAction {
         }
     }
 }
+
+/// Why the item would do nothing if the actor used it now, if so.
+/// Callers check it up front so that no turn is wasted.
+pub fn use_refusal(ecs: &World, actor: Entity, item: Entity) -> Option<String> {
+    // Spent devices do nothing until recharged
+    let spent = ecs
+        .read_storage::<Charges>()
+        .get(item)
+        .is_some_and(|device| device.charges < 1);
+    if spent {
+        return Some(format!("The {} is out of charges.", item_name(ecs, item)));
+    }
+    // Batteries are not wasted when every device is already full
+    if has_component::<Recharges>(ecs, item) && !has_drained_devices(ecs, actor) {
+        return Some("There is nothing to recharge.".to_string());
+    }
+    None
+}
+
+/// Whether the owner carries a device a battery would top up
+fn has_drained_devices(ecs: &World, owner: Entity) -> bool {
+    let inventories = ecs.read_storage::<Inventory>();
+    let consumables = ecs.read_storage::<Consumable>();
+    let charges = ecs.read_storage::<Charges>();
+    inventories.get(owner).is_some_and(|inventory| {
+        inventory.items.iter().any(|item| {
+            !consumables.contains(*item)
+                && charges
+                    .get(*item)
+                    .is_some_and(|device| device.charges < device.max_charges)
+        })
+    })
+}
+
+/// Tiles caught around the target by an area of effect of the given radius.
+/// The blast reaches the first wall or closed door in each direction, smoke does not stop it.
+pub fn area_targets(map: &GameMap, target: Point, radius: i32) -> Targets {
+    if radius == 0 {
+        return Targets::Tile { tile: target };
+    }
+    let mut blast_tiles = Vec::new();
+    for y in target.y - radius..=target.y + radius {
+        for x in target.x - radius..=target.x + radius {
+            let tile = Point::new(x, y);
+            if x < 1 || x > map.width - 2 || y < 1 || y > map.height - 2 {
+                continue;
+            }
+            if DistanceAlg::Pythagoras.distance2d(target, tile) > radius as f32 {
+                continue;
+            }
+            let line = line2d(LineAlg::Bresenham, target, tile);
+            let sheltered = line
+                .iter()
+                .skip(1)
+                .take(line.len().saturating_sub(2))
+                .any(|p| blocks_projectile(map, map.xy_idx(*p)));
+            if !sheltered {
+                blast_tiles.push(tile);
+            }
+        }
+    }
+    Targets::Area { tiles: blast_tiles }
+}
+
+/// Effects the item's components produce when used on the given targets
+pub fn item_effects(
+    ecs: &World,
+    item: Entity,
+    user: Entity,
+    target: Option<Point>,
+    targets: Targets,
+) -> Vec<(EffectType, Targets)> {
+    let mut effects = Vec::new();
+
+    if let Some(area_effect) = ecs.read_storage::<AreaOfEffect>().get(item) {
+        if area_effect.radius > 0 {
+            let effect = EffectType::Particle {
+                glyph: to_cp437('░'),
+                fg: RGB::named(ORANGE),
+                lifetime: 200.0,
+            };
+            effects.push((effect, targets.clone()));
+            if let Some(target) = target {
+                publish(
+                    ecs,
+                    GameEvent::NoiseMade {
+                        pos: target,
+                        volume: 20,
+                    },
+                );
+            }
+        }
+    }
+    if let Some(healing) = ecs.read_storage::<ProvidesHealing>().get(item) {
+        let effect = EffectType::Healing {
+            amount: healing.heal_amount,
+        };
+        effects.push((effect, targets.clone()));
+    }
+    if let Some(damage) = ecs.read_storage::<InflictsDamage>().get(item) {
+        let effect = EffectType::Damage {
+            amount: damage.damage,
+        };
+        effects.push((effect, targets.clone()));
+        // Explosions knock holes in the surrounding terrain
+        if let Targets::Area { .. } | Targets::Tile { .. } = targets {
+            let effect = EffectType::TerrainDamage {
+                amount: damage.damage,
+            };
+            effects.push((effect, targets.clone()));
+        }
+    }
+    if let Some(status) = ecs.read_storage::<InflictsStatus>().get(item) {
+        for inflicted in status.effects.iter() {
+            let effect = EffectType::Status {
+                kind: inflicted.kind,
+                turns: inflicted.turns,
+                potency: inflicted.potency,
+            };
+            effects.push((effect, targets.clone()));
+        }
+    }
+    if let Some(spawns) = ecs.read_storage::<SpawnsEntity>().get(item) {
+        let effect = EffectType::SpawnEntity { kind: spawns.kind };
+        effects.push((effect, targets.clone()));
+    }
+    if let (true, Some(destination)) = (ecs.read_storage::<Teleports>().contains(item), target) {
+        let effect = EffectType::Teleport { destination };
+        effects.push((effect, Targets::Single { target: user }));
+    }
+    if ecs.read_storage::<Recharges>().contains(item) {
+        effects.push((EffectType::Recharge, Targets::Single { target: user }));
+    }
+    if ecs.read_storage::<Identifies>().contains(item) {
+        effects.push((EffectType::Identify, Targets::Single { target: user }));
+    }
+
+    effects
+}
@@ -0,0 +1,61 @@
+use bracket_lib::prelude::*;
+use specs::{Entity, WorldExt};
+
+use crate::{
+    components::*,
+    game_map::{GameMap, TileType},
+    gamelog::GameLog,
+    map::Map,
+};
+
+use super::{has_component, Action};
+
+const VAULT_TURNS: i32 = 2;
+
+pub struct VaultAction {
+    pub window: Point,
+}
+
+impl Action for VaultAction {
+    fn run(&self, actor: Entity, ecs: &mut specs::World) {
+        let is_player = has_component::<Player>(ecs, actor);
+        let mut log = ecs.write_resource::<GameLog>();
+        let map = ecs.read_resource::<GameMap>();
+
+        let storage = ecs.read_storage::<Position>();
+        let actor_pos = storage.get(actor).unwrap().pos;
+
+        let window_idx = map.xy_idx(self.window);
+        if map.tiles[window_idx] != TileType::Window {
+            if is_player {
+                log.log("There is no window to climb through.".to_string());
+            }
+            return;
+        }
+
+        // Land on the other side of the window
+        let destination = self.window + (self.window - actor_pos);
+        if destination.x < 1
+            || destination.x > map.width - 2
+            || destination.y < 1
+            || destination.y > map.height - 2
+            || map.blocked_tiles[map.xy_idx(destination)]
+        {
+            if is_player {
+                log.log("Something blocks the other side.".to_string());
+            }
+            return;
+        }
+
+        if is_player {
+            log.log("You start climbing through the window.".to_string());
+        }
+
+        ecs.write_storage::<Busy>()
+            .insert(actor, Busy { turns: VAULT_TURNS })
+            .expect("Unable to insert busy");
+        ecs.write_storage::<Vaulting>()
+            .insert(actor, Vaulting { destination })
+            .expect("Unable to insert vaulting");
+    }
+}
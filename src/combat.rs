@@ -0,0 +1,167 @@
+use bracket_lib::prelude::*;
+use specs::prelude::*;
+
+use crate::{
+    actions::map_equipped_items_comp,
+    components::{CombatStats, DefenseBonus},
+};
+
+/// Number to beat on a d20 attack roll, before the target's dodge
+const BASE_DIFFICULTY: i32 = 10;
+/// Attack roll malus of suppressed attackers
+pub const SUPPRESSED_ATTACK_PENALTY: i32 = 3;
+/// Accuracy lost by suppressed shooters
+pub const SUPPRESSED_ACCURACY_PENALTY: i32 = 25;
+/// Turns a critical hit leaves the target bleeding for
+pub const CRITICAL_BLEEDING_TURNS: i32 = 3;
+
+pub fn parse_dice(dice: &str) -> DiceType {
+    parse_dice_string(dice).expect("Invalid dice string")
+}
+
+pub fn dice_label(dice: DiceType) -> String {
+    match dice.bonus {
+        0 => format!("{}d{}", dice.n_dice, dice.die_type),
+        bonus if bonus > 0 => format!("{}d{}+{}", dice.n_dice, dice.die_type, bonus),
+        bonus => format!("{}d{}{}", dice.n_dice, dice.die_type, bonus),
+    }
+}
+
+/// Mean result of rolling the dice
+pub fn average_roll(dice: DiceType) -> f32 {
+    dice.n_dice as f32 * (dice.die_type as f32 + 1.0) / 2.0 + dice.bonus as f32
+}
+
+pub enum AttackOutcome {
+    Fumble,
+    Miss,
+    Hit,
+    Critical,
+}
+
+pub struct AttackRoll {
+    pub natural: i32,
+    pub total: i32,
+    pub difficulty: i32,
+    pub outcome: AttackOutcome,
+}
+
+impl AttackRoll {
+    pub fn label(&self) -> String {
+        format!(
+            "d20 {} -> {} vs {}",
+            self.natural, self.total, self.difficulty
+        )
+    }
+}
+
+/// A natural 1 always misses and a natural 20 always lands a critical hit
+pub fn roll_attack(rng: &mut RandomNumberGenerator, bonus: i32, dodge: i32) -> AttackRoll {
+    let natural = rng.roll_dice(1, 20);
+    let total = natural + bonus;
+    let difficulty = BASE_DIFFICULTY + dodge;
+    let outcome = match natural {
+        1 => AttackOutcome::Fumble,
+        20 => AttackOutcome::Critical,
+        _ if total >= difficulty => AttackOutcome::Hit,
+        _ => AttackOutcome::Miss,
+    };
+    AttackRoll {
+        natural,
+        total,
+        difficulty,
+        outcome,
+    }
+}
+
+pub struct DamageRoll {
+    pub rolled: i32,
+    pub armor: i32,
+    pub dealt: i32,
+}
+
+impl DamageRoll {
+    pub fn label(&self, dice: DiceType, critical: bool) -> String {
+        let dice = if critical {
+            format!("2x {}", dice_label(dice))
+        } else {
+            dice_label(dice)
+        };
+        format!("{} -> {} - {} armor", dice, self.rolled, self.armor)
+    }
+}
+
+/// Criticals roll the damage twice. Armor soaks damage but a hit always hurts a little.
+pub fn roll_damage(
+    rng: &mut RandomNumberGenerator,
+    dice: DiceType,
+    critical: bool,
+    armor: i32,
+) -> DamageRoll {
+    let mut rolled = rng.roll(dice);
+    if critical {
+        rolled += rng.roll(dice);
+    }
+    DamageRoll {
+        rolled,
+        armor,
+        dealt: i32::max(1, rolled - armor),
+    }
+}
+
+pub fn total_dodge(ecs: &World, entity: Entity, stats: &CombatStats) -> i32 {
+    stats.dodge
+        + map_equipped_items_comp::<DefenseBonus, i32>(ecs, entity, |db| db.dodge)
+            .iter()
+            .sum::<i32>()
+}
+
+pub fn total_armor(ecs: &World, entity: Entity, stats: &CombatStats) -> i32 {
+    stats.armor
+        + map_equipped_items_comp::<DefenseBonus, i32>(ecs, entity, |db| db.armor)
+            .iter()
+            .sum::<i32>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dice_with_bonus() {
+        let dice = parse_dice("2d6+1");
+        assert_eq!((dice.n_dice, dice.die_type, dice.bonus), (2, 6, 1));
+        assert_eq!(dice_label(dice), "2d6+1");
+        assert_eq!(average_roll(dice), 8.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid dice string")]
+    fn rejects_malformed_dice() {
+        parse_dice("two dice");
+    }
+
+    #[test]
+    fn armor_never_soaks_a_whole_hit() {
+        let mut rng = RandomNumberGenerator::seeded(1);
+        for _ in 0..100 {
+            let roll = roll_damage(&mut rng, parse_dice("1d4"), false, 10);
+            assert_eq!(roll.dealt, 1);
+        }
+    }
+
+    #[test]
+    fn natural_rolls_ignore_modifiers() {
+        let mut rng = RandomNumberGenerator::seeded(1);
+        for _ in 0..200 {
+            let attack = roll_attack(&mut rng, 100, 0);
+            if attack.natural == 1 {
+                assert!(matches!(attack.outcome, AttackOutcome::Fumble));
+            }
+            let attack = roll_attack(&mut rng, 0, 100);
+            if attack.natural == 20 {
+                assert!(matches!(attack.outcome, AttackOutcome::Critical));
+            }
+        }
+    }
+}
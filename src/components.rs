@@ -1,12 +1,16 @@
-use bracket_lib::prelude::{FontCharType, Point, RGB};
+use bracket_lib::prelude::{DiceType, FontCharType, Point, RGB};
 use serde::*;
 #[allow(deprecated)]
 use specs::{error::NoError, prelude::*, saveload::*, Entity};
 use specs_derive::{Component, ConvertSaveload};
 
 use crate::{
+    combat::parse_dice,
     entity_containers::{EntityHashMap, EntityVec},
     game_map::GameMap,
+    identification::ItemIdentification,
+    resources::GameStats,
+    status::{Stacking, StatusKind},
 };
 
 #[derive(Component, ConvertSaveload, Clone)]
@@ -33,6 +37,9 @@ pub struct Viewshed {
     pub visible_tiles: Vec<Point>,
     pub range: i32,
     pub dirty: bool,
+    pub night_vision: bool,
+    /// Half-angle in degrees of the field of view around the facing. Sees all around if None.
+    pub cone: Option<f32>,
 }
 
 impl Viewshed {
@@ -41,6 +48,8 @@ impl Viewshed {
             visible_tiles: Vec::new(),
             dirty: true,
             range,
+            night_vision: false,
+            cone: None,
         }
     }
 }
@@ -57,18 +66,26 @@ pub struct BlocksTile {}
 pub struct CombatStats {
     pub max_hp: i32,
     pub hp: i32,
-    pub defense: i32,
-    pub power: i32,
+    /// Added to the d20 attack roll
+    pub attack: i32,
+    /// Raises the roll needed to hit
+    pub dodge: i32,
+    /// Soaked from the damage of every hit
+    pub armor: i32,
+    /// Unarmed damage
+    pub damage: DiceType,
     pub was_hurt: bool,
 }
 
 impl CombatStats {
-    pub fn new(hp: i32, defense: i32, power: i32) -> CombatStats {
+    pub fn new(hp: i32, attack: i32, dodge: i32, armor: i32, damage: &str) -> CombatStats {
         CombatStats {
             max_hp: hp,
             hp,
-            defense,
-            power,
+            attack,
+            dodge,
+            armor,
+            damage: parse_dice(damage),
             was_hurt: false,
         }
     }
@@ -83,11 +100,124 @@ impl CombatStats {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub turns: i32,
+    pub potency: i32,
+}
+
+/// Timed conditions on a creature, ticked down once per turn
+#[derive(Component, Serialize, Deserialize, Clone, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    pub fn get(&self, kind: StatusKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    pub fn apply(&mut self, kind: StatusKind, turns: i32, potency: i32) {
+        if let Some(opposite) = kind.opposite() {
+            self.effects.retain(|effect| effect.kind != opposite);
+        }
+        match self.effects.iter_mut().find(|effect| effect.kind == kind) {
+            None => self.effects.push(StatusEffect {
+                kind,
+                turns,
+                potency,
+            }),
+            Some(effect) => match kind.stacking() {
+                Stacking::Refresh => {
+                    effect.turns = i32::max(effect.turns, turns);
+                    effect.potency = i32::max(effect.potency, potency);
+                }
+                Stacking::Extend => {
+                    effect.turns += turns;
+                    effect.potency = i32::max(effect.potency, potency);
+                }
+                Stacking::Intensify => {
+                    effect.turns = i32::max(effect.turns, turns);
+                    effect.potency += potency;
+                }
+            },
+        }
+    }
+
+    /// Stunned creatures lose their turns, slowed ones every other turn
+    pub fn loses_turn(&self) -> bool {
+        self.has(StatusKind::Stunned)
+            || self
+                .get(StatusKind::Slowed)
+                .is_some_and(|slowed| slowed.turns % 2 == 0)
+    }
+}
+
+/// The entity is occupied and loses its next turns
 #[derive(Component, ConvertSaveload, Clone)]
-pub struct Confused {
+pub struct Busy {
     pub turns: i32,
 }
 
+/// The entity is climbing through a window and lands on `destination` once no longer busy
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Vaulting {
+    pub destination: Point,
+}
+
+/// The monster heard something and goes to check it out
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Investigating {
+    pub pos: Point,
+}
+
+/// Lights up the tiles around the entity
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct LightSource {
+    pub radius: i32,
+    pub color: RGB,
+    /// Half-angle in degrees of a light shining towards the carrier's facing
+    pub cone: Option<f32>,
+}
+
+/// Direction the entity is looking towards
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Facing {
+    pub direction: Point,
+}
+
+/// Lets the wearer see in the dark
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct NightVision {}
+
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum Caliber {
+    Pistol,
+    Rifle,
+}
+
+/// A gun and the rounds left in its magazine. Accuracy is the chance to hit in percent.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Firearm {
+    pub damage: DiceType,
+    pub accuracy: i32,
+    pub range: i32,
+    pub caliber: Caliber,
+    pub magazine: i32,
+    pub ammo: i32,
+}
+
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Ammunition {
+    pub caliber: Caliber,
+    pub rounds: i32,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct Inventory {
     pub items: EntityVec<Entity>,
@@ -97,6 +227,39 @@ pub struct Inventory {
 pub enum EquipmentSlot {
     Melee,
     Shield,
+    Head,
+    Body,
+    Hands,
+    Feet,
+    Accessory,
+    Ranged,
+}
+
+impl EquipmentSlot {
+    /// Every slot, in the order the equipment screen lists them
+    pub const ALL: [EquipmentSlot; 8] = [
+        EquipmentSlot::Head,
+        EquipmentSlot::Body,
+        EquipmentSlot::Hands,
+        EquipmentSlot::Feet,
+        EquipmentSlot::Melee,
+        EquipmentSlot::Shield,
+        EquipmentSlot::Ranged,
+        EquipmentSlot::Accessory,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EquipmentSlot::Melee => "Melee",
+            EquipmentSlot::Shield => "Shield",
+            EquipmentSlot::Head => "Head",
+            EquipmentSlot::Body => "Body",
+            EquipmentSlot::Hands => "Hands",
+            EquipmentSlot::Feet => "Feet",
+            EquipmentSlot::Accessory => "Accessory",
+            EquipmentSlot::Ranged => "Ranged",
+        }
+    }
 }
 
 #[derive(Component, Serialize, Deserialize, Clone)]
@@ -104,17 +267,51 @@ pub struct Equippable {
     pub slot: EquipmentSlot,
 }
 
+/// Melee weapon held in both hands, leaving the shield slot empty
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct TwoHanded {}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct Equipment {
     pub slots: EntityHashMap<EquipmentSlot, Entity>,
 }
 
+/// Something that can be carried. Components describing an item must also be copied
+/// by `actions::split_stack`, or items taken off a stack lose them.
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct Item {}
 
+/// Remains left behind by a dead creature
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Corpse {}
+
 #[derive(Component, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Consumable {}
 
+/// Kilograms a single item weighs
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Weight {
+    pub kg: f32,
+}
+
+/// Uses left in a device. Consumable devices are used up with their last charge,
+/// the others can be recharged.
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Charges {
+    pub charges: i32,
+    pub max_charges: i32,
+}
+
+/// Refills the charges of the user's rechargeable devices
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Recharges {}
+
+/// Number of identical items held by a stackable item
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct Quantity {
+    pub amount: i32,
+}
+
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct Ranged {
     pub range: i32,
@@ -135,19 +332,65 @@ pub struct ProvidesHealing {
     pub heal_amount: i32,
 }
 
+/// Puts status effects on whoever the item is used on
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct InflictsStatus {
+    pub effects: Vec<StatusEffect>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub enum SpawnKind {
+    BurningFlare,
+    SmokeCloud,
+}
+
+/// Cloud blocking sight for a few turns
 #[derive(Component, ConvertSaveload, Clone)]
-pub struct Confusion {
+pub struct Smoke {
     pub turns: i32,
 }
 
+/// Goes off `fuse` turns after being thrown
 #[derive(Component, ConvertSaveload, Clone)]
-pub struct MeleePowerBonus {
-    pub power: i32,
+pub struct Grenade {
+    pub fuse: i32,
+}
+
+/// Thrown grenade about to go off
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct LitFuse {
+    pub turns: i32,
+}
+
+/// Breaks when thrown, spilling its effects where it lands
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Shatters {}
+
+/// Leaves an entity behind on the targeted tile
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct SpawnsEntity {
+    pub kind: SpawnKind,
+}
+
+/// Identifies everything the user carries
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Identifies {}
+
+/// Moves the user to the targeted tile
+#[derive(Component, Serialize, Deserialize, Clone)]
+pub struct Teleports {}
+
+/// Replaces the wielder's unarmed damage
+#[derive(Component, ConvertSaveload, Clone)]
+pub struct MeleeWeapon {
+    pub damage: DiceType,
+    pub hit_bonus: i32,
 }
 
 #[derive(Component, ConvertSaveload, Clone)]
 pub struct DefenseBonus {
-    pub defense: i32,
+    pub dodge: i32,
+    pub armor: i32,
 }
 
 #[derive(Component, Serialize, Deserialize, Clone)]
@@ -157,7 +400,10 @@ pub struct ParticleLifetime {
 
 pub struct SerializeMe;
 
-#[derive(Component, Serialize, Deserialize, Clone)]
+#[derive(Component, ConvertSaveload, Clone)]
 pub struct SerializationHelper {
     pub map: GameMap,
+    pub stats: GameStats,
+    pub identification: ItemIdentification,
+    pub active_operator: Entity,
 }
@@ -0,0 +1,93 @@
+use specs::prelude::*;
+
+use crate::components::{CombatStats, Equipment, Inventory, Quantity, Weight};
+
+/// Kilograms anyone can carry, before their stats are taken into account
+const BASE_CAPACITY: f32 = 10.0;
+/// Multiple of the capacity past which no more items can be picked up
+const OVERLOAD_FACTOR: f32 = 1.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Encumbrance {
+    Unburdened,
+    /// Moves cost an extra turn
+    Burdened,
+    /// Moves cost an extra turn and nothing more can be picked up
+    Overloaded,
+}
+
+impl Encumbrance {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Encumbrance::Unburdened => "Unburdened",
+            Encumbrance::Burdened => "Burdened",
+            Encumbrance::Overloaded => "Overloaded",
+        }
+    }
+}
+
+/// Kilograms the character carries without slowing down
+pub fn carry_capacity(stats: &CombatStats) -> f32 {
+    BASE_CAPACITY + (stats.max_hp / 2 + stats.attack) as f32
+}
+
+/// Total weight of the inventory and worn equipment
+pub fn carried_weight(ecs: &World, entity: Entity) -> f32 {
+    let weights = ecs.read_storage::<Weight>();
+    let quantities = ecs.read_storage::<Quantity>();
+    let inventories = ecs.read_storage::<Inventory>();
+    let equipments = ecs.read_storage::<Equipment>();
+
+    let carried = inventories
+        .get(entity)
+        .into_iter()
+        .flat_map(|inventory| inventory.items.iter());
+    let worn = equipments
+        .get(entity)
+        .into_iter()
+        .flat_map(|equipment| equipment.slots.values());
+    carried
+        .chain(worn)
+        .map(|item| stack_weight(&weights, &quantities, *item))
+        .sum()
+}
+
+/// Weight of an item, counting every item of a stack
+fn stack_weight(
+    weights: &ReadStorage<Weight>,
+    quantities: &ReadStorage<Quantity>,
+    item: Entity,
+) -> f32 {
+    let weight = weights.get(item).map_or(0.0, |weight| weight.kg);
+    let amount = quantities.get(item).map_or(1, |q| q.amount);
+    weight * amount as f32
+}
+
+/// Whether picking up the item keeps the character within the overload limit
+pub fn can_pick_up(ecs: &World, entity: Entity, item: Entity) -> bool {
+    let capacity = match ecs.read_storage::<CombatStats>().get(entity) {
+        None => return true,
+        Some(stats) => carry_capacity(stats),
+    };
+    let added = stack_weight(
+        &ecs.read_storage::<Weight>(),
+        &ecs.read_storage::<Quantity>(),
+        item,
+    );
+    carried_weight(ecs, entity) + added <= capacity * OVERLOAD_FACTOR
+}
+
+pub fn encumbrance(ecs: &World, entity: Entity) -> Encumbrance {
+    let capacity = match ecs.read_storage::<CombatStats>().get(entity) {
+        None => return Encumbrance::Unburdened,
+        Some(stats) => carry_capacity(stats),
+    };
+    let weight = carried_weight(ecs, entity);
+    if weight > capacity * OVERLOAD_FACTOR {
+        Encumbrance::Overloaded
+    } else if weight > capacity {
+        Encumbrance::Burdened
+    } else {
+        Encumbrance::Unburdened
+    }
+}
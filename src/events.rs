@@ -0,0 +1,44 @@
+use bracket_lib::prelude::Point;
+use specs::{
+    prelude::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+/// Things happening in the game world, for systems to react to
+pub enum GameEvent {
+    EntityDamaged {
+        target: Entity,
+        pos: Point,
+        amount: i32,
+        /// Whether the wound spills blood
+        bleeds: bool,
+    },
+    EntityDied {
+        name: String,
+        pos: Point,
+        was_player: bool,
+    },
+    ItemPickedUp {
+        actor: Entity,
+        item: Entity,
+    },
+    DoorOpened {
+        actor: Entity,
+    },
+    NoiseMade {
+        pos: Point,
+        volume: i32,
+    },
+}
+
+pub fn publish(ecs: &World, event: GameEvent) {
+    ecs.write_resource::<EventChannel<GameEvent>>()
+        .single_write(event);
+}
+
+/// Subscribes a system to the events published from now on
+pub fn register_reader(world: &mut World) -> ReaderId<GameEvent> {
+    world
+        .fetch_mut::<EventChannel<GameEvent>>()
+        .register_reader()
+}
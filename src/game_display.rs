@@ -42,6 +42,7 @@ impl GameDisplay {
         let poi = ecs.read_resource::<PointsOfInterest>();
         draw_map(&map, &poi, ctx);
         draw_renderables(ecs, ctx);
+        draw_active_operator(ecs, ctx);
         draw_ui(ecs, ctx);
         draw_screen(ecs, ctx, self.mode)
     }
@@ -67,29 +68,29 @@ pub fn draw_map(map: &GameMap, poi: &PointsOfInterest, ctx: &mut BTerm) {
     let mut x = 0;
     for (idx, tile) in map.tiles.iter().enumerate() {
         if map.revealed_tiles[idx] {
-            let glyph;
-            let mut fg;
+            let def = tile.def();
+            let mut glyph = to_cp437(def.glyph);
+            let mut fg = def.fg();
             let pos = map.idx_xy(idx);
-            match tile {
-                TileType::Floor => {
-                    glyph = to_cp437('.');
-                    fg = RGB::from_f32(0.0, 0.5, 0.5);
-                }
-                TileType::Wall => {
-                    glyph = wall_glyph(&*map, pos);
-                    fg = RGB::from_f32(0., 1.0, 0.);
-                }
-                TileType::Door => {
-                    glyph = to_cp437('≡');
-                    fg = RGB::from_f32(0., 1.0, 0.);
-                }
-                TileType::Window => {
-                    glyph = to_cp437('∩');
-                    fg = RGB::from_f32(0., 1.0, 0.);
+            match map.door_state(idx) {
+                Some(DoorState::Open) => glyph = to_cp437('\''),
+                Some(DoorState::Broken) => {
+                    glyph = to_cp437(',');
+                    fg = RGB::from_f32(0.5, 0.5, 0.);
                 }
+                Some(DoorState::Locked) => fg = RGB::from_f32(1.0, 0.5, 0.),
+                _ => {}
+            }
+            if *tile == TileType::Wall {
+                glyph = wall_glyph(map, pos);
             }
             if !map.visible_tiles[idx] {
                 fg = fg.to_greyscale()
+            } else if map.is_seen_through_night_vision(idx) {
+                fg = night_vision_color(fg);
+            } else if let Some(light) = map.light.get(idx) {
+                // Tint by the light falling on the tile, keeping dark tiles readable
+                fg = fg * (*light * 0.7 + 0.3);
             }
 
             let bg = if poi.contains(pos) {
@@ -97,7 +98,7 @@ pub fn draw_map(map: &GameMap, poi: &PointsOfInterest, ctx: &mut BTerm) {
             } else if let Some(decal) = map.decal_tiles.get(&idx) {
                 decal.color
             } else {
-                RGB::from_f32(0., 0., 0.)
+                def.bg().unwrap_or_else(|| RGB::from_f32(0., 0., 0.))
             };
             ctx.set(x, y, fg, bg, glyph);
         }
@@ -111,6 +112,12 @@ pub fn draw_map(map: &GameMap, poi: &PointsOfInterest, ctx: &mut BTerm) {
     }
 }
 
+/// Monochrome green rendering of what is seen through night vision goggles
+fn night_vision_color(fg: RGB) -> RGB {
+    let level = fg.to_greyscale().g;
+    RGB::from_f32(0.0, 0.3 + 0.7 * level, 0.0)
+}
+
 fn wall_glyph(map: &GameMap, pos: Point) -> FontCharType {
     if pos.x < 1 || pos.x > map.width - 2 || pos.y < 1 || pos.y > map.height - 2 {
         return 35;
@@ -156,11 +163,31 @@ pub fn draw_renderables(ecs: &World, ctx: &mut BTerm) {
     let renderables = ecs.read_storage::<Renderable>();
     let map = ecs.read_resource::<GameMap>();
     let mut data = (&positions, &renderables).join().collect::<Vec<_>>();
-    data.sort_by(|&a, &b| b.1.render_order.cmp(&a.1.render_order));
+    data.sort_by_key(|&(_, render)| std::cmp::Reverse(render.render_order));
+
+    // Entities last seen on tiles now out of sight
+    for (idx, memory) in map.remembered_entities.iter() {
+        if !map.visible_tiles[*idx] {
+            let pos = map.idx_xy(*idx);
+            let fg = memory.fg.to_greyscale();
+            ctx.set(pos.x, pos.y, fg, RGB::named(BLACK), memory.glyph);
+        }
+    }
+
     for (pos, render) in data {
         let idx = map.xy_idx(pos.pos);
         if map.visible_tiles[idx] {
-            ctx.set(pos.pos.x, pos.pos.y, render.fg, render.bg, render.glyph)
+            let fg = if map.is_seen_through_night_vision(idx) {
+                night_vision_color(render.fg)
+            } else {
+                render.fg
+            };
+            ctx.set(pos.pos.x, pos.pos.y, fg, render.bg, render.glyph)
         }
     }
 }
+
+fn draw_active_operator(ecs: &World, ctx: &mut BTerm) {
+    let player_pos = ecs.read_resource::<PlayerPos>();
+    ctx.set_bg(player_pos.pos.x, player_pos.pos.y, RGB::named(DARK_GREEN));
+}
@@ -1,16 +1,49 @@
 use bracket_lib::prelude::*;
 use serde::{Deserialize, Serialize};
 use specs::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::map::Map;
+use crate::{
+    map::Map,
+    tile_defs::{tile_def, TileDef},
+};
 
-#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+/// Light level under which tiles can only be seen from up close
+pub const LIGHT_THRESHOLD: f32 = 0.3;
+const OUTDOOR_LIGHT: f32 = 0.4;
+const INDOOR_LIGHT: f32 = 0.1;
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum TileType {
     Wall,
     Floor,
     Door,
     Window,
+    BrokenWindow,
+    Rubble,
+    Grass,
+    ShallowWater,
+    Table,
+}
+
+impl TileType {
+    pub fn def(self) -> &'static TileDef {
+        tile_def(self)
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub enum DoorState {
+    Open,
+    Closed,
+    Locked,
+    Broken,
+}
+
+impl DoorState {
+    pub fn is_closed(self) -> bool {
+        self == DoorState::Closed || self == DoorState::Locked
+    }
 }
 
 #[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
@@ -26,6 +59,13 @@ impl Decal {
     }
 }
 
+/// How an entity looked when the squad last saw it
+#[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct RememberedEntity {
+    pub glyph: FontCharType,
+    pub fg: RGB,
+}
+
 #[derive(Default, Serialize, Deserialize, Clone)]
 pub struct GameMap {
     pub tiles: Vec<TileType>,
@@ -33,10 +73,25 @@ pub struct GameMap {
     pub visible_tiles: Vec<bool>,
     pub blocked_tiles: Vec<bool>,
     pub decal_tiles: HashMap<usize, Decal>,
+    pub door_tiles: HashMap<usize, DoorState>,
+    pub tile_damage: HashMap<usize, i32>,
+    pub remembered_entities: HashMap<usize, RememberedEntity>,
+    /// Tiles filled with smoke, which blocks sight
+    pub smoke_tiles: HashSet<usize>,
 
     #[serde(skip_serializing)]
     #[serde(skip_deserializing)]
     pub entities_tiles: Vec<Vec<Entity>>,
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
+    pub ambient_light: Vec<f32>,
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
+    pub light: Vec<RGB>,
+    /// Dark tiles the squad only sees through night vision
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
+    pub night_vision_tiles: HashSet<usize>,
     pub rooms: Vec<Rect>,
     pub width: i32,
     pub height: i32,
@@ -44,40 +99,38 @@ pub struct GameMap {
 
 impl BaseMap for GameMap {
     fn is_opaque(&self, idx: usize) -> bool {
-        self.tiles[idx] == TileType::Wall || self.tiles[idx] == TileType::Door
+        if self.smoke_tiles.contains(&idx) {
+            return true;
+        }
+        let opaque = self.tiles[idx].def().opaque;
+        match self.door_state(idx) {
+            // Doors only block sight while closed
+            Some(state) => opaque && state.is_closed(),
+            None => opaque,
+        }
     }
 
     fn get_available_exits(&self, idx: usize) -> SmallVec<[(usize, f32); 10]> {
         let mut exits = SmallVec::new();
         let x = idx as i32 % self.width;
         let y = idx as i32 / self.width;
-        let w = self.width as usize;
-
-        // Cardinal directions
-        if self.is_exit_valid(Point { x: x - 1, y: y }) {
-            exits.push((idx - 1, 1.0))
-        };
-        if self.is_exit_valid(Point { x: x + 1, y: y }) {
-            exits.push((idx + 1, 1.0))
-        };
-        if self.is_exit_valid(Point { x: x, y: y - 1 }) {
-            exits.push((idx - w, 1.0))
-        };
-        if self.is_exit_valid(Point { x: x, y: y + 1 }) {
-            exits.push((idx + w, 1.0))
-        };
 
-        if self.is_exit_valid(Point { x: x - 1, y: y - 1 }) {
-            exits.push(((idx - w) - 1, 1.45));
-        }
-        if self.is_exit_valid(Point { x: x + 1, y: y - 1 }) {
-            exits.push(((idx - w) + 1, 1.45));
-        }
-        if self.is_exit_valid(Point { x: x - 1, y: y + 1 }) {
-            exits.push(((idx + w) - 1, 1.45));
-        }
-        if self.is_exit_valid(Point { x: x + 1, y: y + 1 }) {
-            exits.push(((idx + w) + 1, 1.45));
+        // Cardinal directions, then diagonals
+        let neighbors = [
+            (Point { x: x - 1, y }, 1.0),
+            (Point { x: x + 1, y }, 1.0),
+            (Point { x, y: y - 1 }, 1.0),
+            (Point { x, y: y + 1 }, 1.0),
+            (Point { x: x - 1, y: y - 1 }, 1.45),
+            (Point { x: x + 1, y: y - 1 }, 1.45),
+            (Point { x: x - 1, y: y + 1 }, 1.45),
+            (Point { x: x + 1, y: y + 1 }, 1.45),
+        ];
+        for (pos, distance) in neighbors {
+            if self.is_exit_valid(pos) {
+                let exit_idx = self.xy_idx(pos);
+                exits.push((exit_idx, distance * self.movement_cost(exit_idx)));
+            }
         }
 
         exits
@@ -103,12 +156,13 @@ impl Map for GameMap {
 }
 
 impl GameMap {
-    fn is_exit_valid(&self, pos: Point) -> bool {
+    pub fn is_exit_valid(&self, pos: Point) -> bool {
         if pos.x < 1 || pos.x > self.width - 1 || pos.y < 1 || pos.y > self.height - 1 {
             return false;
         }
         let idx = self.xy_idx(pos);
-        !self.blocked_tiles[idx as usize]
+        // Closed doors can be opened on the way
+        !self.blocked_tiles[idx] || self.door_state(idx) == Some(DoorState::Closed)
     }
 
     pub fn clear_content_index(&mut self) {
@@ -118,11 +172,124 @@ impl GameMap {
     }
 
     pub fn populate_blocked(&mut self) {
-        for (i, tile) in self.tiles.iter_mut().enumerate() {
-            self.blocked_tiles[i] = *tile == TileType::Wall || *tile == TileType::Window;
+        for i in 0..self.tiles.len() {
+            self.blocked_tiles[i] = !self.tiles[i].def().walkable || self.is_door_closed(i);
+        }
+    }
+
+    /// Cost of entering a tile, relative to plain floor
+    pub fn movement_cost(&self, idx: usize) -> f32 {
+        let cost = self.tiles[idx].def().movement_cost;
+        // Opening a closed door on the way takes an extra turn
+        if self.is_door_closed(idx) {
+            cost + 1.0
+        } else {
+            cost
         }
     }
 
+    /// Turns spent on top of the move itself when entering a tile
+    pub fn extra_move_turns(&self, idx: usize) -> i32 {
+        (self.tiles[idx].def().movement_cost - 1.0) as i32
+    }
+
+    pub fn door_state(&self, idx: usize) -> Option<DoorState> {
+        if self.tiles[idx] != TileType::Door {
+            return None;
+        }
+        Some(
+            self.door_tiles
+                .get(&idx)
+                .copied()
+                .unwrap_or(DoorState::Closed),
+        )
+    }
+
+    pub fn set_door_state(&mut self, idx: usize, state: DoorState) {
+        self.door_tiles.insert(idx, state);
+    }
+
+    /// Applies damage to a destructible tile. Returns true if the tile was destroyed.
+    pub fn damage_tile(&mut self, idx: usize, amount: i32) -> bool {
+        let pos = self.idx_xy(idx);
+        if pos.x < 1 || pos.x > self.width - 2 || pos.y < 1 || pos.y > self.height - 2 {
+            return false;
+        }
+        if self.door_state(idx) == Some(DoorState::Broken) {
+            return false;
+        }
+        let hit_points = match self.tiles[idx].def().hit_points {
+            None => return false,
+            Some(hit_points) => hit_points,
+        };
+
+        let damage = self.tile_damage.entry(idx).or_insert(0);
+        *damage += amount;
+        if *damage < hit_points {
+            return false;
+        }
+
+        self.tile_damage.remove(&idx);
+        self.destroy_tile(idx);
+        true
+    }
+
+    /// Shatters an intact window. Returns true if there was one.
+    pub fn break_window(&mut self, idx: usize) -> bool {
+        if self.tiles[idx] != TileType::Window {
+            return false;
+        }
+        self.tile_damage.remove(&idx);
+        self.destroy_tile(idx);
+        true
+    }
+
+    fn destroy_tile(&mut self, idx: usize) {
+        if self.tiles[idx] == TileType::Door {
+            self.set_door_state(idx, DoorState::Broken);
+        } else {
+            let def = self.tiles[idx].def();
+            if let Some(color) = def.debris() {
+                self.decal_tiles.insert(idx, Decal { color });
+            }
+            self.tiles[idx] = def.destroyed_into.unwrap_or(TileType::Rubble);
+        }
+        self.blocked_tiles[idx] = false;
+    }
+
+    pub fn is_door_closed(&self, idx: usize) -> bool {
+        self.door_state(idx).is_some_and(DoorState::is_closed)
+    }
+
+    /// Moonlight outside, near darkness inside buildings
+    pub fn compute_ambient_light(&mut self) {
+        self.ambient_light = (0..self.tiles.len())
+            .map(|idx| {
+                let pos = self.idx_xy(idx);
+                if self.rooms.iter().any(|room| room.point_in_rect(pos)) {
+                    INDOOR_LIGHT
+                } else {
+                    OUTDOOR_LIGHT
+                }
+            })
+            .collect();
+    }
+
+    pub fn light_level(&self, idx: usize) -> f32 {
+        match self.light.get(idx) {
+            None => 1.0,
+            Some(light) => light.r.max(light.g).max(light.b),
+        }
+    }
+
+    pub fn is_seen_through_night_vision(&self, idx: usize) -> bool {
+        self.night_vision_tiles.contains(&idx)
+    }
+
+    pub fn is_lit(&self, idx: usize) -> bool {
+        self.light_level(idx) >= LIGHT_THRESHOLD
+    }
+
     pub fn is_revealed_and_wall(&self, pos: Point) -> bool {
         let idx = self.xy_idx(pos);
         self.tiles[idx] == TileType::Wall && self.revealed_tiles[idx]
@@ -1,13 +1,14 @@
 use bracket_lib::prelude::*;
 
-pub fn show_selection<T>(ctx: &mut BTerm, title: &str, options: &Vec<(String, T)>) {
+pub fn show_selection<T>(ctx: &mut BTerm, title: &str, options: &[(String, T)]) {
     let count = options.len();
 
-    let mut y = (25 - (count / 2)) as i32;
+    let y = (25 - (count / 2)) as i32;
+    let width = usize::max(31, title.len() + 6) as i32;
     ctx.draw_box(
         15,
         y - 2,
-        31,
+        width,
         (count + 3) as i32,
         RGB::named(WHITE),
         RGB::named(BLACK),
@@ -21,11 +22,8 @@ pub fn show_selection<T>(ctx: &mut BTerm, title: &str, options: &Vec<(String, T)
         "ESCAPE to cancel",
     );
 
-    let mut j = 0;
-    for (name, _entity) in options {
-        ctx.print(18, y, format_option(j, name));
-        y += 1;
-        j += 1;
+    for (j, (name, _entity)) in options.iter().enumerate() {
+        ctx.print(18, y + j as i32, format_option(j, name));
     }
 }
 
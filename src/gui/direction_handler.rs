@@ -0,0 +1,57 @@
+use bracket_lib::prelude::*;
+use specs::{World, WorldExt};
+
+use crate::{
+    actions::*,
+    game_display::{GameSignal, UiSignal},
+    input::*,
+    resources::PlayerPos,
+};
+
+use super::gui_handlers::UiHandler;
+
+#[derive(PartialEq, Copy, Clone)]
+pub enum DirectionPurpose {
+    CloseDoor,
+    Kick,
+    Vault,
+}
+
+#[derive(PartialEq, Copy, Clone)]
+pub struct DirectionHandler {
+    pub purpose: DirectionPurpose,
+}
+
+impl UiHandler for DirectionHandler {
+    type Output = Point;
+
+    fn show(&self, _ecs: &World, ctx: &mut BTerm) {
+        let title = match self.purpose {
+            DirectionPurpose::CloseDoor => "Close door in which direction?",
+            DirectionPurpose::Kick => "Kick in which direction?",
+            DirectionPurpose::Vault => "Climb through which window?",
+        };
+        ctx.print_color(5, 0, RGB::named(YELLOW), RGB::named(BLACK), title);
+    }
+
+    fn read_input(&self, _ecs: &World, ctx: &mut BTerm) -> ItemMenuResult<Self::Output> {
+        let input = map_all(ctx.key, &[map_direction, map_look_commands]);
+        match input {
+            Some(Command::Direction { direction }) => ItemMenuResult::Selected {
+                result: get_direction_offset(direction),
+            },
+            Some(Command::Cancel) => ItemMenuResult::Cancel,
+            _ => ItemMenuResult::NoResponse,
+        }
+    }
+
+    fn handle(&self, ecs: &World, input: Point) -> UiSignal {
+        let target = ecs.read_resource::<PlayerPos>().pos + input;
+        let action: AnyAction = match self.purpose {
+            DirectionPurpose::CloseDoor => Box::new(CloseDoorAction { target }),
+            DirectionPurpose::Kick => Box::new(KickAction { target }),
+            DirectionPurpose::Vault => Box::new(VaultAction { window: target }),
+        };
+        UiSignal::GameSignal(GameSignal::Perform(action))
+    }
+}
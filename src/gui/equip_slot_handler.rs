@@ -0,0 +1,54 @@
+use bracket_lib::prelude::BTerm;
+use specs::{Entity, World, WorldExt};
+
+use crate::{
+    actions::EquipItemAction,
+    components::{EquipmentSlot, Equippable, Inventory, Name},
+    game_display::{GameSignal, UiSignal},
+    input::{read_input_selection, ItemMenuResult},
+    resources::PlayerEntity,
+};
+
+use super::{components::show_selection, gui_handlers::UiHandler};
+
+/// Picks a carried item to put in an empty equipment slot
+#[derive(PartialEq, Copy, Clone)]
+pub struct EquipSlotHandler {
+    pub slot: EquipmentSlot,
+}
+
+impl UiHandler for EquipSlotHandler {
+    type Output = Entity;
+
+    fn show(&self, ecs: &World, ctx: &mut BTerm) {
+        let options = get_slot_options(ecs, self.slot);
+        let title = format!("Equip {}", self.slot.label());
+        show_selection(ctx, &title, &options)
+    }
+
+    fn read_input(&self, ecs: &World, ctx: &mut BTerm) -> ItemMenuResult<Self::Output> {
+        let options = get_slot_options(ecs, self.slot);
+        read_input_selection(ctx.key, &options)
+    }
+
+    fn handle(&self, _ecs: &World, input: Entity) -> UiSignal {
+        let action = EquipItemAction { target: input };
+        UiSignal::GameSignal(GameSignal::Perform(Box::new(action)))
+    }
+}
+
+/// Carried items that fit the slot
+fn get_slot_options(ecs: &World, slot: EquipmentSlot) -> Vec<(String, Entity)> {
+    let player_entity = ecs.read_resource::<PlayerEntity>();
+    let names = ecs.read_storage::<Name>();
+    let equippables = ecs.read_storage::<Equippable>();
+    let storage = ecs.read_storage::<Inventory>();
+    let player_inventory = storage.get(player_entity.entity).unwrap();
+
+    player_inventory
+        .items
+        .iter()
+        .filter(|item| equippables.get(**item).is_some_and(|e| e.slot == slot))
+        .map(|item| (names.get(*item).unwrap().name.clone(), *item))
+        .collect()
+}
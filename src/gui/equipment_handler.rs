@@ -0,0 +1,69 @@
+use bracket_lib::prelude::BTerm;
+use specs::{World, WorldExt};
+
+use crate::{
+    components::{Equipment, EquipmentSlot, Name, TwoHanded},
+    game_display::UiSignal,
+    input::{read_input_selection, ItemMenuResult},
+    resources::PlayerEntity,
+};
+
+use super::{
+    components::show_selection,
+    gui_handlers::{UiHandler, UiScreen},
+};
+
+#[derive(PartialEq, Copy, Clone)]
+pub struct EquipmentHandler {}
+
+impl UiHandler for EquipmentHandler {
+    type Output = EquipmentSlot;
+
+    fn show(&self, ecs: &World, ctx: &mut BTerm) {
+        let options = get_equipment_options(ecs);
+        show_selection(ctx, "Equipment", &options)
+    }
+
+    fn read_input(&self, ecs: &World, ctx: &mut BTerm) -> ItemMenuResult<Self::Output> {
+        let options = get_equipment_options(ecs);
+        read_input_selection(ctx.key, &options)
+    }
+
+    fn handle(&self, ecs: &World, input: EquipmentSlot) -> UiSignal {
+        let player_entity = ecs.read_resource::<PlayerEntity>();
+        let storage = ecs.read_storage::<Equipment>();
+        let player_equipment = storage.get(player_entity.entity).unwrap();
+        match player_equipment.slots.get(&input) {
+            Some(item) => UiSignal::UpdateScreen(UiScreen::UseItem { item: *item }),
+            None => UiSignal::UpdateScreen(UiScreen::EquipSlot { slot: input }),
+        }
+    }
+}
+
+/// Every slot with the item worn in it
+fn get_equipment_options(ecs: &World) -> Vec<(String, EquipmentSlot)> {
+    let player_entity = ecs.read_resource::<PlayerEntity>();
+    let names = ecs.read_storage::<Name>();
+    let two_handed = ecs.read_storage::<TwoHanded>();
+
+    let storage = ecs.read_storage::<Equipment>();
+    let player_equipment = storage.get(player_entity.entity).unwrap();
+    let wields_two_handed = player_equipment
+        .slots
+        .get(&EquipmentSlot::Melee)
+        .is_some_and(|weapon| two_handed.contains(*weapon));
+
+    EquipmentSlot::ALL
+        .iter()
+        .map(|slot| {
+            let worn = match player_equipment.slots.get(slot) {
+                Some(item) => names.get(*item).unwrap().name.clone(),
+                None if *slot == EquipmentSlot::Shield && wields_two_handed => {
+                    "(two-handed)".to_string()
+                }
+                None => "-".to_string(),
+            };
+            (format!("{}: {}", slot.label(), worn), *slot)
+        })
+        .collect()
+}
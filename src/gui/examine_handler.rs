@@ -2,8 +2,11 @@ use bracket_lib::prelude::*;
 use specs::{World, WorldExt};
 
 use crate::{
+    components::{Monster, Viewshed},
     game_display::UiSignal,
+    game_map::GameMap,
     input::*,
+    map::Map,
     resources::PointsOfInterest,
 };
 
@@ -20,12 +23,13 @@ pub struct ExamineHandler {
 impl UiHandler for ExamineHandler {
     type Output = Point;
 
-    fn show(&self, _ecs: &World, ctx: &mut BTerm) {
+    fn show(&self, ecs: &World, ctx: &mut BTerm) {
         ctx.print_color(5, 0, RGB::named(YELLOW), RGB::named(BLACK), "Examine mode");
 
         let pos = self.selection;
+        draw_vision_cone(ecs, ctx, pos);
         ctx.set_bg(pos.x, pos.y, RGB::named(CYAN));
-        draw_tooltips(_ecs, ctx, pos);
+        draw_tooltips(ecs, ctx, pos);
     }
 
     fn read_input(&self, ecs: &World, ctx: &mut BTerm) -> ItemMenuResult<Self::Output> {
@@ -55,3 +59,30 @@ impl UiHandler for ExamineHandler {
         UiSignal::UpdateScreen(UiScreen::Examine { selection: input })
     }
 }
+
+/// Highlights what a visible monster under the cursor can see
+fn draw_vision_cone(ecs: &World, ctx: &mut BTerm, pos: Point) {
+    let map = ecs.read_resource::<GameMap>();
+    if pos.x < 0 || pos.x >= map.width || pos.y < 0 || pos.y >= map.height {
+        return;
+    }
+    let idx = map.xy_idx(pos);
+    if !map.visible_tiles[idx] {
+        return;
+    }
+
+    let monsters = ecs.read_storage::<Monster>();
+    let viewsheds = ecs.read_storage::<Viewshed>();
+    for entity in map.entities_tiles[idx].iter() {
+        if !monsters.contains(*entity) {
+            continue;
+        }
+        if let Some(viewshed) = viewsheds.get(*entity) {
+            for tile in viewshed.visible_tiles.iter() {
+                if map.revealed_tiles[map.xy_idx(*tile)] {
+                    ctx.set_bg(tile.x, tile.y, RGB::from_f32(0.4, 0.1, 0.1));
+                }
+            }
+        }
+    }
+}
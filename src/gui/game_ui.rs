@@ -1,7 +1,18 @@
 use bracket_lib::prelude::*;
 use specs::prelude::*;
 
-use crate::{components::*, constants::UI_HEIGHT, game_map::GameMap, gamelog::GameLog, map::Map};
+use crate::{
+    actions::equipped_firearm,
+    components::*,
+    constants::UI_HEIGHT,
+    game_map::GameMap,
+    gamelog::GameLog,
+    identification::ItemIdentification,
+    map::Map,
+    projectile::cover_from,
+    resources::{PlayerEntity, PlayerPos},
+    tile_defs::Cover,
+};
 
 pub fn draw_ui(ecs: &World, ctx: &mut BTerm) {
     let map = ecs.read_resource::<GameMap>();
@@ -16,8 +27,14 @@ pub fn draw_ui(ecs: &World, ctx: &mut BTerm) {
 
     let combat_stats = ecs.read_storage::<CombatStats>();
     let players = ecs.read_storage::<Player>();
-    for (_player, stats) in (&players, &combat_stats).join() {
-        let health = format!(" HP: {} / {} ", stats.hp, stats.max_hp);
+    let names = ecs.read_storage::<Name>();
+    let player_entity = ecs.read_resource::<PlayerEntity>();
+
+    if let (Some(stats), Some(name)) = (
+        combat_stats.get(player_entity.entity),
+        names.get(player_entity.entity),
+    ) {
+        let health = format!(" {} HP: {} / {} ", name.name, stats.hp, stats.max_hp);
         ctx.print_color(
             12,
             map.height,
@@ -27,9 +44,9 @@ pub fn draw_ui(ecs: &World, ctx: &mut BTerm) {
         );
 
         ctx.draw_bar_horizontal(
-            28,
+            36,
             map.height,
-            map.width - 28 - 1,
+            map.width / 2 - 36,
             stats.hp,
             stats.max_hp,
             RGB::named(RED),
@@ -37,6 +54,62 @@ pub fn draw_ui(ecs: &World, ctx: &mut BTerm) {
         );
     }
 
+    // Whether the active operator stands out in the light
+    let player_pos = ecs.read_resource::<PlayerPos>();
+    let (label, fg) = if map.is_lit(map.xy_idx(player_pos.pos)) {
+        (" Lit ", RGB::named(YELLOW))
+    } else {
+        (" Hidden ", RGB::named(GREY))
+    };
+    ctx.print_color(2, map.height, fg, RGB::named(BLACK), label);
+
+    // Ranged weapon and rounds left in the magazine
+    if let Some(weapon) = equipped_firearm(ecs, player_entity.entity) {
+        let firearms = ecs.read_storage::<Firearm>();
+        let firearm = firearms.get(weapon).unwrap();
+        let status = format!(
+            " {} {}/{} ",
+            names.get(weapon).unwrap().name,
+            firearm.ammo,
+            firearm.magazine
+        );
+        ctx.print_color(
+            2,
+            map.height + UI_HEIGHT - 1,
+            RGB::named(WHITE),
+            RGB::named(BLACK),
+            &status,
+        );
+    }
+
+    // Status effects of the active operator
+    let statuses = ecs.read_storage::<StatusEffects>();
+    if let Some(status) = statuses.get(player_entity.entity) {
+        let y = map.height + UI_HEIGHT - 1;
+        let mut x = map.width / 2 + 2;
+        for effect in status.effects.iter() {
+            let (glyph, fg) = effect.kind.icon();
+            ctx.set(x, y, fg, RGB::named(BLACK), glyph);
+            let label = format!("{} {} ", effect.kind.label(), effect.turns);
+            ctx.print_color(x + 1, y, fg, RGB::named(BLACK), &label);
+            x += label.len() as i32 + 2;
+        }
+    }
+
+    // Squad roster
+    let mut x = map.width / 2 + 2;
+    for (entity, _player, stats, name) in (&ecs.entities(), &players, &combat_stats, &names).join()
+    {
+        let fg = if entity == player_entity.entity {
+            RGB::named(YELLOW)
+        } else {
+            RGB::named(WHITE)
+        };
+        let status = format!(" {} {}/{} ", name.name, stats.hp, stats.max_hp);
+        ctx.print_color(x, map.height, fg, RGB::named(BLACK), &status);
+        x += status.len() as i32 + 1;
+    }
+
     let log = ecs.read_resource::<GameLog>();
 
     let mut y = map.height + 1;
@@ -60,12 +133,38 @@ pub fn draw_tooltips(ecs: &World, ctx: &mut BTerm, pos: Point) {
     if pos.x >= map.width || pos.y >= map.height {
         return;
     }
+    let combat_stats = ecs.read_storage::<CombatStats>();
+    let statuses = ecs.read_storage::<StatusEffects>();
+    let identification = ecs.read_resource::<ItemIdentification>();
+    let player_pos = ecs.read_resource::<PlayerPos>();
     let mut tooltip: Vec<String> = Vec::new();
-    for (name, position) in (&names, &positions).join() {
+    for (entity, name, position) in (&ecs.entities(), &names, &positions).join() {
         let idx = map.xy_idx(position.pos);
         if position.pos == pos && map.visible_tiles[idx] {
-            tooltip.push(name.name.to_string());
+            let mut properties = Vec::new();
+            let cover = cover_from(&map, player_pos.pos, pos);
+            if combat_stats.contains(entity) && cover != Cover::None {
+                properties.push(cover.label());
+            }
+            if let Some(status) = statuses.get(entity) {
+                properties.extend(status.effects.iter().map(|effect| effect.kind.label()));
+            }
+            let name = identification.display_name(&name.name);
+            if properties.is_empty() {
+                tooltip.push(name);
+            } else {
+                tooltip.push(format!("{} ({})", name, properties.join(", ")));
+            }
+        }
+    }
+    let idx = map.xy_idx(pos);
+    if map.revealed_tiles[idx] {
+        let def = map.tiles[idx].def();
+        let mut properties = vec![def.cover.label()];
+        if def.flammable {
+            properties.push("flammable");
         }
+        tooltip.push(format!("{} ({})", def.name, properties.join(", ")));
     }
 
     if tooltip.is_empty() {
@@ -83,8 +182,7 @@ pub fn draw_tooltips(ecs: &World, ctx: &mut BTerm, pos: Point) {
     if pos.x > 40 {
         let arrow_pos = Point::new(pos.x - 2, pos.y);
         let left_x = pos.x - width;
-        let mut y = pos.y;
-        for s in tooltip.iter() {
+        for (y, s) in (pos.y..).zip(tooltip.iter()) {
             ctx.print_color(left_x, y, RGB::named(WHITE), RGB::named(GREY), s);
             let padding = (width - s.len() as i32) - 1;
             for i in 0..padding {
@@ -93,23 +191,21 @@ pub fn draw_tooltips(ecs: &World, ctx: &mut BTerm, pos: Point) {
                     y,
                     RGB::named(WHITE),
                     RGB::named(GREY),
-                    &" ".to_string(),
+                    " ".to_string(),
                 );
             }
-            y += 1;
         }
         ctx.print_color(
             arrow_pos.x,
             arrow_pos.y,
             RGB::named(WHITE),
             RGB::named(GREY),
-            &"->".to_string(),
+            "->".to_string(),
         );
     } else {
         let arrow_pos = Point::new(pos.x + 1, pos.y);
         let left_x = pos.x + 3;
-        let mut y = pos.y;
-        for s in tooltip.iter() {
+        for (y, s) in (pos.y..).zip(tooltip.iter()) {
             ctx.print_color(left_x + 1, y, RGB::named(WHITE), RGB::named(GREY), s);
             let padding = (width - s.len() as i32) - 1;
             for i in 0..padding {
@@ -118,17 +214,16 @@ pub fn draw_tooltips(ecs: &World, ctx: &mut BTerm, pos: Point) {
                     y,
                     RGB::named(WHITE),
                     RGB::named(GREY),
-                    &" ".to_string(),
+                    " ".to_string(),
                 );
             }
-            y += 1;
         }
         ctx.print_color(
             arrow_pos.x,
             arrow_pos.y,
             RGB::named(WHITE),
             RGB::named(GREY),
-            &"<-".to_string(),
+            "<-".to_string(),
         );
     }
 }
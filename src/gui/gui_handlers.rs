@@ -1,12 +1,18 @@
 use bracket_lib::prelude::*;
 use specs::*;
 
-use crate::{game_display::UiSignal, input::ItemMenuResult};
+use crate::{components::EquipmentSlot, game_display::UiSignal, input::ItemMenuResult};
 
 use super::{
-    equipped_item_handler::EquippedItemHandler, examine_handler::ExamineHandler,
-    inventory_handler::InventoryHandler, play_handler::PlayHandler,
-    targeting_handler::TargetingHandler, use_item_handler::UseItemHandler,
+    direction_handler::{DirectionHandler, DirectionPurpose},
+    equip_slot_handler::EquipSlotHandler,
+    equipment_handler::EquipmentHandler,
+    examine_handler::ExamineHandler,
+    inventory_handler::InventoryHandler,
+    play_handler::PlayHandler,
+    targeting_handler::{TargetingHandler, TargetingPurpose},
+    throw_handler::ThrowHandler,
+    use_item_handler::UseItemHandler,
 };
 
 #[derive(PartialEq, Copy, Clone)]
@@ -15,6 +21,7 @@ pub enum ItemUsage {
     Drop,
     Equip,
     Unequip,
+    Throw,
 }
 
 #[derive(PartialEq, Copy, Clone)]
@@ -23,15 +30,22 @@ pub enum UiScreen {
     UseItem {
         item: Entity,
     },
-    RemoveItem,
+    Equipment,
+    EquipSlot {
+        slot: EquipmentSlot,
+    },
     Targeting {
         range: i32,
-        item: Entity,
+        purpose: TargetingPurpose,
         selection: Point,
     },
     Examine {
         selection: Point,
     },
+    Direction {
+        purpose: DirectionPurpose,
+    },
+    Throw,
     Play,
 }
 
@@ -41,16 +55,19 @@ fn get_screen_handler(screen: UiScreen) -> Box<dyn UiHandlerMin> {
         UiScreen::Inventory => Box::new(InventoryHandler {}),
         UiScreen::Targeting {
             range,
-            item,
+            purpose,
             selection,
         } => Box::new(TargetingHandler {
             range,
-            item,
+            purpose,
             selection,
         }),
-        UiScreen::RemoveItem => Box::new(EquippedItemHandler {}),
+        UiScreen::Equipment => Box::new(EquipmentHandler {}),
+        UiScreen::EquipSlot { slot } => Box::new(EquipSlotHandler { slot }),
         UiScreen::Examine { selection } => Box::new(ExamineHandler { selection }),
         UiScreen::UseItem { item } => Box::new(UseItemHandler { item }),
+        UiScreen::Direction { purpose } => Box::new(DirectionHandler { purpose }),
+        UiScreen::Throw => Box::new(ThrowHandler {}),
     }
 }
 
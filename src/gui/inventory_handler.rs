@@ -1,11 +1,11 @@
-use std::collections::HashMap;
-
 use bracket_lib::prelude::BTerm;
 use specs::{Entity, World, WorldExt};
 
 use crate::{
-    components::{Inventory, Name},
+    components::{Charges, CombatStats, Inventory, Quantity},
+    encumbrance::{carried_weight, carry_capacity, encumbrance, Encumbrance},
     game_display::UiSignal,
+    identification::item_name,
     resources::PlayerEntity, input::{ItemMenuResult, read_input_selection},
 };
 
@@ -22,7 +22,7 @@ impl UiHandler for InventoryHandler {
 
     fn show(&self, ecs: &World, ctx: &mut BTerm) {
         let options = get_inventory_options(ecs);
-        show_selection(ctx, "Inventory", &options);
+        show_selection(ctx, &inventory_title(ecs), &options);
     }
 
     fn read_input(&self, ecs: &World, ctx: &mut BTerm) -> ItemMenuResult<Self::Output> {
@@ -35,30 +35,49 @@ impl UiHandler for InventoryHandler {
     }
 }
 
-fn get_inventory_options(ecs: &World) -> Vec<(String, Entity)> {
+/// Shows the carried weight against the capacity, and how burdened the player is
+fn inventory_title(ecs: &World) -> String {
+    let player_entity = ecs.read_resource::<PlayerEntity>().entity;
+    let combat_stats = ecs.read_storage::<CombatStats>();
+    let capacity = carry_capacity(combat_stats.get(player_entity).unwrap());
+    let weight = carried_weight(ecs, player_entity);
+    match encumbrance(ecs, player_entity) {
+        Encumbrance::Unburdened => format!("Inventory {:.1}/{:.0} kg", weight, capacity),
+        load => format!(
+            "Inventory {:.1}/{:.0} kg, {}",
+            weight,
+            capacity,
+            load.label()
+        ),
+    }
+}
+
+pub fn get_inventory_options(ecs: &World) -> Vec<(String, Entity)> {
     let player_entity = ecs.read_resource::<PlayerEntity>();
-    let names = ecs.read_storage::<Name>();
+    let quantities = ecs.read_storage::<Quantity>();
+    let charges = ecs.read_storage::<Charges>();
     let storage = ecs.read_storage::<Inventory>();
     let player_inventory = storage.get(player_entity.entity).unwrap();
-    let options: Vec<(String, Entity)> = player_inventory
+    let mut items: Vec<(String, Entity)> = player_inventory
         .items
         .iter()
-        .map(|entity| (names.get(*entity).unwrap().name.clone(), *entity))
-        .collect();
-
-    let mut count_dict = HashMap::<String, i32>::new();
-    let mut entity_dict = HashMap::<String, Entity>::new();
-    for (name, e) in options.iter() {
-        entity_dict.entry(name.clone()).or_insert(*e);
-        let counter = count_dict.entry(name.clone()).or_insert(0);
-        *counter += 1;
-    }
-
-    let mut new_options: Vec<(String, Entity)> = count_dict
-        .iter()
-        .map(|(name, count)| (format!("{} ({})", *name, *count), entity_dict[name]))
+        .map(|entity| (item_name(ecs, *entity), *entity))
         .collect();
+    items.sort_by(|a, b| a.0.cmp(&b.0));
 
-    new_options.sort();
-    new_options
+    items
+        .into_iter()
+        .map(|(name, entity)| {
+            let label = match (quantities.get(entity), charges.get(entity)) {
+                (Some(quantity), _) if quantity.amount > 1 => {
+                    format!("{}x {}", quantity.amount, name)
+                }
+                (_, Some(device)) => {
+                    format!("{} ({}/{})", name, device.charges, device.max_charges)
+                }
+                _ => name,
+            };
+            (label, entity)
+        })
+        .collect()
 }
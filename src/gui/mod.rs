@@ -1,9 +1,12 @@
 pub mod components;
-pub mod equipped_item_handler;
+pub mod direction_handler;
+pub mod equip_slot_handler;
+pub mod equipment_handler;
 pub mod examine_handler;
 pub mod game_ui;
 pub mod gui_handlers;
 pub mod inventory_handler;
 pub mod play_handler;
 pub mod targeting_handler;
+pub mod throw_handler;
 pub mod use_item_handler;
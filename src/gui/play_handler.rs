@@ -5,16 +5,21 @@ use specs::*;
 
 use crate::{
     actions::*,
-    components::{CombatStats, Item, Position},
+    components::{CombatStats, Firearm, Item, Player, Position},
     game_display::{GameSignal, UiSignal},
     game_map::GameMap,
     gamelog::GameLog,
     input::*,
     map::Map,
-    resources::{PlayerEntity, PlayerPos},
+    resources::{DebugOptions, PlayerEntity, PlayerPos},
+    squad::cycle_operator,
 };
 
-use super::gui_handlers::{UiHandler, UiScreen};
+use super::{
+    direction_handler::DirectionPurpose,
+    gui_handlers::{UiHandler, UiScreen},
+    targeting_handler::TargetingPurpose,
+};
 
 #[derive(PartialEq, Copy, Clone)]
 pub struct PlayHandler {}
@@ -44,20 +49,86 @@ impl UiHandler for PlayHandler {
                     UiSignal::None
                 }
             }
-            Command::ShowInventory => return UiSignal::UpdateScreen(UiScreen::Inventory),
-            Command::ShowRemoveItem => return UiSignal::UpdateScreen(UiScreen::RemoveItem),
+            Command::ShowInventory => UiSignal::UpdateScreen(UiScreen::Inventory),
+            Command::ShowEquipment => UiSignal::UpdateScreen(UiScreen::Equipment),
             Command::ExamineMode => {
                 let player_pos = ecs.read_resource::<PlayerPos>();
-                return UiSignal::UpdateScreen(UiScreen::Examine {
+                UiSignal::UpdateScreen(UiScreen::Examine {
                     selection: player_pos.pos,
-                });
+                })
+            }
+            Command::CloseDoor => UiSignal::UpdateScreen(UiScreen::Direction {
+                purpose: DirectionPurpose::CloseDoor,
+            }),
+            Command::Kick => UiSignal::UpdateScreen(UiScreen::Direction {
+                purpose: DirectionPurpose::Kick,
+            }),
+            Command::Vault => UiSignal::UpdateScreen(UiScreen::Direction {
+                purpose: DirectionPurpose::Vault,
+            }),
+            Command::Fire => try_fire(ecs),
+            Command::Reload => {
+                let player_entity = ecs.read_resource::<PlayerEntity>().entity;
+                let weapon = match equipped_firearm(ecs, player_entity) {
+                    None => {
+                        let mut log = ecs.write_resource::<GameLog>();
+                        log.log("You have no firearm to reload.".to_string());
+                        return UiSignal::None;
+                    }
+                    Some(weapon) => weapon,
+                };
+                if is_magazine_full(ecs, weapon) {
+                    let mut log = ecs.write_resource::<GameLog>();
+                    log.log("The magazine is already full.".to_string());
+                    return UiSignal::None;
+                }
+                if !has_ammo_for(ecs, player_entity, weapon) {
+                    let mut log = ecs.write_resource::<GameLog>();
+                    log.log("You have no ammo for this firearm.".to_string());
+                    return UiSignal::None;
+                }
+                UiSignal::GameSignal(GameSignal::Perform(Box::new(ReloadAction {})))
             }
-            Command::SaveQuit => return UiSignal::GameSignal(GameSignal::SaveQuit),
+            Command::Throw => UiSignal::UpdateScreen(UiScreen::Throw),
+            Command::ToggleRollDebug => {
+                let mut debug = ecs.write_resource::<DebugOptions>();
+                debug.show_rolls = !debug.show_rolls;
+                let mut log = ecs.write_resource::<GameLog>();
+                log.log(format!(
+                    "Combat rolls {}.",
+                    if debug.show_rolls { "shown" } else { "hidden" }
+                ));
+                UiSignal::None
+            }
+            Command::CycleOperator => {
+                cycle_operator(ecs);
+                UiSignal::None
+            }
+            Command::SaveQuit => UiSignal::GameSignal(GameSignal::SaveQuit),
             _ => UiSignal::None,
         }
     }
 }
 
+fn try_fire(ecs: &World) -> UiSignal {
+    let player_entity = ecs.read_resource::<PlayerEntity>().entity;
+    let weapon = match equipped_firearm(ecs, player_entity) {
+        None => {
+            let mut log = ecs.write_resource::<GameLog>();
+            log.log("You have no firearm equipped.".to_string());
+            return UiSignal::None;
+        }
+        Some(weapon) => weapon,
+    };
+    let firearms = ecs.read_storage::<Firearm>();
+    let player_pos = ecs.read_resource::<PlayerPos>();
+    UiSignal::UpdateScreen(UiScreen::Targeting {
+        range: firearms.get(weapon).unwrap().range,
+        purpose: TargetingPurpose::Fire,
+        selection: player_pos.pos,
+    })
+}
+
 pub fn try_move_player(direction: Direction, ecs: &World) -> UiSignal {
     let player_entity = ecs.read_resource::<PlayerEntity>().entity;
     let action = {
@@ -66,11 +137,12 @@ pub fn try_move_player(direction: Direction, ecs: &World) -> UiSignal {
             storage.get(player_entity).unwrap().pos
         };
         let combat_stats = ecs.read_storage::<CombatStats>();
+        let players = ecs.read_storage::<Player>();
         let map = ecs.read_resource::<GameMap>();
 
         let offset = get_direction_offset(direction);
 
-        get_player_action(&map, player_pos, offset, &combat_stats)
+        get_player_action(&map, player_pos, offset, &combat_stats, &players)
     };
 
     if let Some(action) = action {
@@ -85,9 +157,14 @@ pub fn get_player_action(
     player_pos: Point,
     offset: Point,
     combat_stats: &ReadStorage<CombatStats>,
+    players: &ReadStorage<Player>,
 ) -> Option<AnyAction> {
     let destination_idx = map.xy_idx(player_pos + offset);
     for potential_target in map.entities_tiles[destination_idx].iter() {
+        // Operators do not attack their teammates, nor walk over them
+        if players.contains(*potential_target) {
+            return None;
+        }
         let target = combat_stats.get(*potential_target);
         match target {
             None => {}
@@ -100,6 +177,14 @@ pub fn get_player_action(
         }
     }
 
+    let destination = player_pos + offset;
+    if map.is_door_closed(destination_idx) {
+        let action = OpenDoorAction {
+            target: destination,
+        };
+        return Some(Box::new(action));
+    }
+
     if !map.blocked_tiles[destination_idx] {
         let target = Point {
             x: min(map.width - 1, max(0, player_pos.x + offset.x)),
@@ -109,7 +194,7 @@ pub fn get_player_action(
         return Some(Box::new(action));
     }
 
-    return None;
+    None
 }
 
 pub fn grab_item(ecs: &World) -> Option<AnyAction> {
@@ -2,19 +2,29 @@ use bracket_lib::prelude::*;
 use specs::{Entity, World, WorldExt};
 
 use crate::{
-    actions::UseItemAction,
-    components::Viewshed,
+    actions::{equipped_firearm, FireAction, ThrowAction, UseItemAction},
+    components::{AreaOfEffect, Firearm, Viewshed},
     game_display::{GameSignal, UiSignal},
+    game_map::GameMap,
     input::{get_direction_offset, map_all, map_direction, map_look_commands, Command, ItemMenuResult},
+    map::Map,
+    projectile::{blocks_projectile, cover_from, hit_chance, line_of_fire},
     resources::{PlayerEntity, PlayerPos, PointsOfInterest},
 };
 
 use super::gui_handlers::{UiHandler, UiScreen};
 
+#[derive(PartialEq, Copy, Clone)]
+pub enum TargetingPurpose {
+    UseItem { item: Entity },
+    Fire,
+    Throw { item: Entity },
+}
+
 #[derive(PartialEq, Copy, Clone)]
 pub struct TargetingHandler {
     pub range: i32,
-    pub item: Entity,
+    pub purpose: TargetingPurpose,
     pub selection: Point,
 }
 
@@ -27,13 +37,20 @@ impl UiHandler for TargetingHandler {
     type Output = LookCommand;
 
     fn show(&self, ecs: &World, ctx: &mut BTerm) {
-        ctx.print_color(
-            5,
-            0,
-            RGB::named(YELLOW),
-            RGB::named(BLACK),
-            "Select Target:",
-        );
+        let mut header = "Select Target:".to_string();
+        if self.fires_projectile(ecs) {
+            let map = ecs.read_resource::<GameMap>();
+            let player_pos = ecs.read_resource::<PlayerPos>();
+            let cover = cover_from(&map, player_pos.pos, self.selection);
+            if self.purpose == TargetingPurpose::Fire {
+                if let Some(accuracy) = self.firearm_accuracy(ecs) {
+                    let chance = hit_chance(accuracy, cover);
+                    header = format!("{} {}% to hit,", header, chance);
+                }
+            }
+            header = format!("{} {}", header, cover.label());
+        }
+        ctx.print_color(5, 0, RGB::named(YELLOW), RGB::named(BLACK), &header);
 
         let available_cells = get_cells_in_range(ecs, self.range);
 
@@ -41,6 +58,10 @@ impl UiHandler for TargetingHandler {
             ctx.set_bg(tile.x, tile.y, RGB::named(BLUE));
         }
 
+        if self.fires_projectile(ecs) {
+            draw_line_of_fire(ecs, ctx, self.selection, self.range);
+        }
+
         let pos = self.selection;
         let color = if available_cells.contains(&pos) {
             RGB::named(CYAN)
@@ -79,22 +100,70 @@ impl UiHandler for TargetingHandler {
 
     fn handle(&self, _ecs: &World, input: LookCommand) -> UiSignal {
         match input {
-            LookCommand::Select(selection) => {
-                let action = UseItemAction {
-                    item: self.item,
-                    target: Some(selection),
-                };
-                UiSignal::GameSignal(GameSignal::Perform(Box::new(action)))
-            }
+            LookCommand::Select(selection) => match self.purpose {
+                TargetingPurpose::UseItem { item } => {
+                    let action = UseItemAction {
+                        item,
+                        target: Some(selection),
+                    };
+                    UiSignal::GameSignal(GameSignal::Perform(Box::new(action)))
+                }
+                TargetingPurpose::Fire => {
+                    let action = FireAction { target: selection };
+                    UiSignal::GameSignal(GameSignal::Perform(Box::new(action)))
+                }
+                TargetingPurpose::Throw { item } => {
+                    let action = ThrowAction {
+                        item,
+                        target: selection,
+                    };
+                    UiSignal::GameSignal(GameSignal::Perform(Box::new(action)))
+                }
+            },
             LookCommand::Inspect(point) => UiSignal::UpdateScreen(UiScreen::Targeting {
                 range: self.range,
-                item: self.item,
+                purpose: self.purpose,
                 selection: point,
             }),
         }
     }
 }
 
+impl TargetingHandler {
+    fn fires_projectile(&self, ecs: &World) -> bool {
+        match self.purpose {
+            TargetingPurpose::UseItem { item } => {
+                !ecs.read_storage::<AreaOfEffect>().contains(item)
+            }
+            TargetingPurpose::Fire => true,
+            TargetingPurpose::Throw { .. } => false,
+        }
+    }
+
+    fn firearm_accuracy(&self, ecs: &World) -> Option<i32> {
+        let player_entity = ecs.read_resource::<PlayerEntity>();
+        let weapon = equipped_firearm(ecs, player_entity.entity)?;
+        let firearms = ecs.read_storage::<Firearm>();
+        Some(firearms.get(weapon)?.accuracy)
+    }
+}
+
+/// Previews the path up to the target, showing where it would be stopped
+fn draw_line_of_fire(ecs: &World, ctx: &mut BTerm, target: Point, range: i32) {
+    let map = ecs.read_resource::<GameMap>();
+    let player_pos = ecs.read_resource::<PlayerPos>();
+    for tile in line_of_fire(&map, player_pos.pos, target, range) {
+        if tile == target {
+            break;
+        }
+        if blocks_projectile(&map, map.xy_idx(tile)) {
+            ctx.set_bg(tile.x, tile.y, RGB::named(RED));
+            break;
+        }
+        ctx.set_bg(tile.x, tile.y, RGB::named(OLIVE));
+    }
+}
+
 pub fn get_cells_in_range(ecs: &World, range: i32) -> Vec<Point> {
     let player_entity = ecs.read_resource::<PlayerEntity>();
     let player_pos = ecs.read_resource::<PlayerPos>();
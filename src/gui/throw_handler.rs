@@ -0,0 +1,48 @@
+use bracket_lib::prelude::BTerm;
+use specs::{Entity, World, WorldExt};
+
+use crate::{
+    actions::THROW_RANGE,
+    game_display::UiSignal,
+    input::{read_input_selection, ItemMenuResult},
+    resources::PlayerPos,
+};
+
+use super::{
+    components::show_selection,
+    gui_handlers::{UiHandler, UiScreen},
+    inventory_handler::get_inventory_options,
+    targeting_handler::TargetingPurpose,
+};
+
+/// Picks a carried item to throw
+#[derive(PartialEq, Copy, Clone)]
+pub struct ThrowHandler {}
+
+impl UiHandler for ThrowHandler {
+    type Output = Entity;
+
+    fn show(&self, ecs: &World, ctx: &mut BTerm) {
+        let options = get_inventory_options(ecs);
+        show_selection(ctx, "Throw", &options);
+    }
+
+    fn read_input(&self, ecs: &World, ctx: &mut BTerm) -> ItemMenuResult<Self::Output> {
+        let options = get_inventory_options(ecs);
+        read_input_selection(ctx.key, &options)
+    }
+
+    fn handle(&self, ecs: &World, input: Entity) -> UiSignal {
+        throw_targeting(ecs, input)
+    }
+}
+
+/// Aims the item within throwing range
+pub fn throw_targeting(ecs: &World, item: Entity) -> UiSignal {
+    let player_pos = ecs.read_resource::<PlayerPos>();
+    UiSignal::UpdateScreen(UiScreen::Targeting {
+        range: THROW_RANGE,
+        purpose: TargetingPurpose::Throw { item },
+        selection: player_pos.pos,
+    })
+}
@@ -5,11 +5,16 @@ use crate::{
     actions::*,
     components::*,
     game_display::{GameSignal, UiSignal},
+    gamelog::GameLog,
     gui::components::*,
     resources::{PlayerEntity, PlayerPos}, input::{ItemMenuResult, read_input_selection},
 };
 
-use super::gui_handlers::{ItemUsage, UiHandler, UiScreen};
+use super::{
+    gui_handlers::{ItemUsage, UiHandler, UiScreen},
+    targeting_handler::TargetingPurpose,
+    throw_handler::throw_targeting,
+};
 
 #[derive(PartialEq, Copy, Clone)]
 pub struct UseItemHandler {
@@ -44,13 +49,20 @@ impl UiHandler for UseItemHandler {
                 UiSignal::GameSignal(GameSignal::Perform(Box::new(action)))
             }
             ItemUsage::Use => {
-                return try_use_item(ecs, self.item);
+                try_use_item(ecs, self.item)
             }
+            ItemUsage::Throw => throw_targeting(ecs, self.item),
         }
     }
 }
 
 fn try_use_item(ecs: &World, input: Entity) -> UiSignal {
+    // Refuse up front rather than waste a turn
+    let player_entity = ecs.read_resource::<PlayerEntity>().entity;
+    if let Some(reason) = use_refusal(ecs, player_entity, input) {
+        ecs.write_resource::<GameLog>().log(reason);
+        return UiSignal::None;
+    }
     {
         let is_ranged = ecs.read_storage::<Ranged>();
         let is_item_ranged = is_ranged.get(input);
@@ -58,7 +70,7 @@ fn try_use_item(ecs: &World, input: Entity) -> UiSignal {
             let player_pos = ecs.read_resource::<PlayerPos>();
             return UiSignal::UpdateScreen(UiScreen::Targeting {
                 range: is_item_ranged.range,
-                item: input,
+                purpose: TargetingPurpose::UseItem { item: input },
                 selection: player_pos.pos,
             });
         }
@@ -75,7 +87,8 @@ pub fn get_usage_options(ecs: &World, item: Entity) -> Vec<(String, ItemUsage)>
     let mut options = Vec::new();
 
     let consumable = ecs.read_storage::<Consumable>();
-    if consumable.contains(item) {
+    let charges = ecs.read_storage::<Charges>();
+    if consumable.contains(item) || charges.contains(item) {
         options.push(("use".to_string(), ItemUsage::Use));
     }
 
@@ -90,6 +103,7 @@ pub fn get_usage_options(ecs: &World, item: Entity) -> Vec<(String, ItemUsage)>
         if equippable.contains(item) {
             options.push(("equip".to_string(), ItemUsage::Equip));
         }
+        options.push(("throw".to_string(), ItemUsage::Throw));
         options.push(("drop".to_string(), ItemUsage::Drop));
     }
 
@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+
+use bracket_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+
+use crate::{
+    components::{Inventory, Name},
+    gamelog::GameLog,
+};
+
+pub const HEALTH_POTION: &str = "Health Potion";
+pub const STIM_INJECTOR: &str = "Stim Injector";
+pub const MAGIC_MISSILE_SCROLL: &str = "Magic Missile Scroll";
+pub const FIREBALL_SCROLL: &str = "Fireball Scroll";
+pub const CONFUSION_SCROLL: &str = "Confusion Scroll";
+pub const TELEPORT_SCROLL: &str = "Teleport Scroll";
+pub const IDENTIFY_SCROLL: &str = "Identify Scroll";
+
+/// Items that hide behind a random appearance until identified
+const POTIONS: [&str; 2] = [HEALTH_POTION, STIM_INJECTOR];
+const SCROLLS: [&str; 5] = [
+    MAGIC_MISSILE_SCROLL,
+    FIREBALL_SCROLL,
+    CONFUSION_SCROLL,
+    TELEPORT_SCROLL,
+    IDENTIFY_SCROLL,
+];
+
+const POTION_LOOKS: [(&str, (u8, u8, u8)); 6] = [
+    ("Bubbling Red Vial", RED),
+    ("Murky Green Vial", GREEN),
+    ("Cloudy White Vial", WHITE),
+    ("Fizzing Blue Vial", BLUE),
+    ("Oily Black Vial", DARK_GRAY),
+    ("Glowing Yellow Vial", YELLOW),
+];
+const SCROLL_LOOKS: [(&str, (u8, u8, u8)); 7] = [
+    ("Crumpled Pink Scroll", PINK),
+    ("Torn Grey Scroll", GREY),
+    ("Singed Orange Scroll", ORANGE),
+    ("Faded Cyan Scroll", CYAN),
+    ("Stained Brown Scroll", BROWN1),
+    ("Glossy Purple Scroll", PURPLE),
+    ("Blotched Green Scroll", LIME_GREEN),
+];
+
+// Every disguised item needs a look of its own
+const _: () = assert!(POTION_LOOKS.len() >= POTIONS.len());
+const _: () = assert!(SCROLL_LOOKS.len() >= SCROLLS.len());
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Appearance {
+    pub name: String,
+    pub color: (u8, u8, u8),
+}
+
+/// Appearances rolled for this run, and the items the squad has learnt to recognise
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ItemIdentification {
+    appearances: HashMap<String, Appearance>,
+    identified: HashSet<String>,
+}
+
+impl ItemIdentification {
+    pub fn new(rng: &mut RandomNumberGenerator) -> ItemIdentification {
+        let mut appearances = HashMap::new();
+        for (items, looks) in [
+            (&POTIONS[..], &POTION_LOOKS[..]),
+            (&SCROLLS[..], &SCROLL_LOOKS[..]),
+        ] {
+            for (item, (name, color)) in items.iter().zip(shuffled(rng, looks)) {
+                let appearance = Appearance {
+                    name: name.to_string(),
+                    color,
+                };
+                appearances.insert(item.to_string(), appearance);
+            }
+        }
+        ItemIdentification {
+            appearances,
+            identified: HashSet::new(),
+        }
+    }
+
+    /// The name the player knows the item by
+    pub fn display_name(&self, name: &str) -> String {
+        match self.appearances.get(name) {
+            Some(appearance) if !self.identified.contains(name) => appearance.name.clone(),
+            _ => name.to_string(),
+        }
+    }
+
+    pub fn color(&self, name: &str) -> Option<RGB> {
+        self.appearances
+            .get(name)
+            .map(|appearance| RGB::named(appearance.color))
+    }
+
+    /// Returns whether the item was unknown until now
+    pub fn identify(&mut self, name: &str) -> bool {
+        self.appearances.contains_key(name) && self.identified.insert(name.to_string())
+    }
+}
+
+fn shuffled<T: Copy>(rng: &mut RandomNumberGenerator, items: &[T]) -> Vec<T> {
+    let mut items = items.to_vec();
+    for i in (1..items.len()).rev() {
+        let j = rng.range(0, i + 1);
+        items.swap(i, j);
+    }
+    items
+}
+
+/// Name of the item as the player knows it
+pub fn item_name(ecs: &World, item: Entity) -> String {
+    let names = ecs.read_storage::<Name>();
+    let identification = ecs.read_resource::<ItemIdentification>();
+    identification.display_name(&names.get(item).unwrap().name)
+}
+
+/// Learns what the item is, telling the player when it was unknown
+pub fn identify(ecs: &World, item: Entity) {
+    let appearance = item_name(ecs, item);
+    let name = ecs.read_storage::<Name>().get(item).unwrap().name.clone();
+    if ecs.write_resource::<ItemIdentification>().identify(&name) {
+        let mut log = ecs.write_resource::<GameLog>();
+        log.log(format!("The {} is a {}.", appearance, name));
+    }
+}
+
+/// Identifies everything the owner carries
+pub fn identify_carried(ecs: &World, owner: Entity) {
+    let items: Vec<Entity> = match ecs.read_storage::<Inventory>().get(owner) {
+        None => return,
+        Some(inventory) => inventory.items.iter().copied().collect(),
+    };
+    for item in items {
+        identify(ecs, item);
+    }
+}
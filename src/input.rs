@@ -6,8 +6,16 @@ pub enum Command {
     Wait,
     Grab,
     ShowInventory,
-    ShowRemoveItem,
+    ShowEquipment,
     ExamineMode,
+    CycleOperator,
+    CloseDoor,
+    Kick,
+    Vault,
+    Fire,
+    Reload,
+    Throw,
+    ToggleRollDebug,
     SaveQuit,
     Validate,
     Cancel,
@@ -78,8 +86,16 @@ pub fn map_other_commands(key: VirtualKeyCode) -> Option<Command> {
         VirtualKeyCode::W => Some(Command::Wait),
         VirtualKeyCode::G => Some(Command::Grab),
         VirtualKeyCode::I => Some(Command::ShowInventory),
-        VirtualKeyCode::R => Some(Command::ShowRemoveItem),
+        VirtualKeyCode::R => Some(Command::ShowEquipment),
         VirtualKeyCode::X => Some(Command::ExamineMode),
+        VirtualKeyCode::Tab => Some(Command::CycleOperator),
+        VirtualKeyCode::C => Some(Command::CloseDoor),
+        VirtualKeyCode::D => Some(Command::Kick),
+        VirtualKeyCode::V => Some(Command::Vault),
+        VirtualKeyCode::F => Some(Command::Fire),
+        VirtualKeyCode::E => Some(Command::Reload),
+        VirtualKeyCode::T => Some(Command::Throw),
+        VirtualKeyCode::F12 => Some(Command::ToggleRollDebug),
         VirtualKeyCode::Escape => Some(Command::SaveQuit),
         _ => None,
     }
@@ -124,7 +140,7 @@ pub enum ItemMenuResult<T> {
 
 pub fn read_input_selection<T: Copy>(
     key: Option<VirtualKeyCode>,
-    options: &Vec<(String, T)>,
+    options: &[(String, T)],
 ) -> ItemMenuResult<T> {
     let count = options.len();
 
@@ -1,20 +1,28 @@
 mod actions;
+mod combat;
 mod components;
 mod constants;
+mod encumbrance;
 mod entity_containers;
+mod events;
 mod game_display;
 mod game_map;
 mod gamelog;
 mod gui;
+mod identification;
 mod input;
 mod map;
 mod map_generation;
+mod projectile;
 mod random_table;
 mod resources;
 mod scenes;
 mod spawner;
+mod squad;
 mod state;
+mod status;
 mod systems;
+mod tile_defs;
 
 use bracket_lib::prelude::{main_loop, BError, BTermBuilder};
 use constants::*;
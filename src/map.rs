@@ -14,3 +14,15 @@ pub trait Map {
         Point { x, y }
     }
 }
+
+/// Whether a tile lies within the cone of the given half-angle, in degrees, around a direction
+pub fn in_cone(origin: Point, direction: Point, tile: Point, half_angle: f32) -> bool {
+    if tile == origin {
+        return true;
+    }
+    let to_tile = tile - origin;
+    let angle =
+        (to_tile.y as f32).atan2(to_tile.x as f32) - (direction.y as f32).atan2(direction.x as f32);
+    let angle = angle.sin().atan2(angle.cos());
+    angle.abs().to_degrees() <= half_angle
+}
@@ -17,6 +17,7 @@ struct NeighBor {
 
 pub struct BuildingsGenerator {
     pub tiles: Vec<TileType>,
+    pub door_tiles: HashMap<usize, DoorState>,
     pub width: i32,
     pub height: i32,
     max_idx: usize,
@@ -40,19 +41,24 @@ impl MapGenerator for BuildingsGenerator {
             rooms.append(&mut building_rooms);
         }
 
-        let map = GameMap {
+        GameMap {
             tiles: self.tiles.clone(),
             revealed_tiles: vec![false; self.max_idx],
             visible_tiles: vec![false; self.max_idx],
             blocked_tiles: vec![false; self.max_idx],
             entities_tiles: vec![Vec::new(); self.max_idx],
+            ambient_light: Vec::new(),
+            light: Vec::new(),
+            night_vision_tiles: HashSet::new(),
+            smoke_tiles: HashSet::new(),
             decal_tiles: HashMap::new(),
-            rooms: rooms,
+            door_tiles: self.door_tiles.clone(),
+            tile_damage: HashMap::new(),
+            remembered_entities: HashMap::new(),
+            rooms,
             width: self.width,
             height: self.height,
-        };
-
-        map
+        }
     }
 }
 
@@ -62,6 +68,7 @@ impl BuildingsGenerator {
         let rng = RandomNumberGenerator::new();
         BuildingsGenerator {
             tiles: vec![TileType::Floor; max_idx],
+            door_tiles: HashMap::new(),
             width,
             height,
             max_idx,
@@ -110,8 +117,27 @@ impl BuildingsGenerator {
     fn place_point(&mut self, point: Point, tile: TileType) {
         let idx = self.xy_idx(point);
         if idx > 0 && idx < self.max_idx {
-            self.tiles[idx as usize] = tile;
+            self.tiles[idx] = tile;
+            self.door_tiles.remove(&idx);
+        }
+    }
+
+    fn place_door(&mut self, point: Point, state: DoorState) {
+        self.place_point(point, TileType::Door);
+        let idx = self.xy_idx(point);
+        if self.tiles[idx] == TileType::Door {
+            self.door_tiles.insert(idx, state);
+        }
+    }
+
+    fn roll_door_state(&mut self, can_be_locked: bool) -> DoorState {
+        let mut door_table = RandomTable::<DoorState>::new(&mut self.rng)
+            .add(DoorState::Open, 4)
+            .add(DoorState::Closed, 6);
+        if can_be_locked {
+            door_table = door_table.add(DoorState::Locked, 3);
         }
+        *door_table.roll()
     }
 
     fn roll_room(&mut self) -> Rect {
@@ -122,8 +148,7 @@ impl BuildingsGenerator {
         let h = self.rng.range(MIN_SIZE, MAX_SIZE);
         let x = self.rng.range(1, self.width - w - 1);
         let y = self.rng.range(1, self.height - h - 1);
-        let new_room = Rect::with_size(x, y, w, h);
-        new_room
+        Rect::with_size(x, y, w, h)
     }
 
     fn generate_interior(&mut self, building: Rect) -> Vec<Rect> {
@@ -145,7 +170,7 @@ impl BuildingsGenerator {
         rooms
     }
 
-    fn connect_rooms(&mut self, rooms: &Vec<Rect>, building: Rect) {
+    fn connect_rooms(&mut self, rooms: &[Rect], building: Rect) {
         let neighbor_graph = scan_for_neighbors(rooms, building);
 
         // randomly walk the neighbor graph to ensure connectivity
@@ -169,21 +194,23 @@ impl BuildingsGenerator {
     }
 
     fn add_random_doors(&mut self, neighbor_graph: &HashMap<i32, Vec<NeighBor>>) {
-        let rooms_vec: Vec<i32> = neighbor_graph.keys().map(|i| *i).collect();
+        let rooms_vec: Vec<i32> = neighbor_graph.keys().copied().collect();
         for _i in 0..8 {
             let room_idx = rooms_vec.get(self.rng.range(0, rooms_vec.len())).unwrap();
-            let neighbors = neighbor_graph.get(&room_idx).unwrap();
+            let neighbors = neighbor_graph.get(room_idx).unwrap();
             let connection = neighbors.get(self.rng.range(0, neighbors.len())).unwrap();
             if connection
                 .shared_wall
                 .iter()
                 .all(|point| self.tiles[self.xy_idx(*point)] == TileType::Wall)
             {
-                let wall = connection
+                let wall = *connection
                     .shared_wall
                     .get(self.rng.range(0, connection.shared_wall.len()))
                     .unwrap();
-                self.place_point(*wall, TileType::Door);
+                // Extra doors are not needed for connectivity, so they may be locked
+                let state = self.roll_door_state(true);
+                self.place_door(wall, state);
             }
         }
     }
@@ -198,22 +225,22 @@ impl BuildingsGenerator {
             .iter()
             .map(|n| n.idx)
             .collect();
-        to_connect = to_connect.union(&ext_neighbors).map(|i| *i).collect();
+        to_connect = to_connect.union(&ext_neighbors).copied().collect();
         while !to_connect.is_empty() {
-            let mut to_connect_vec: Vec<i32> = to_connect.iter().map(|i| *i).collect();
+            let mut to_connect_vec: Vec<i32> = to_connect.iter().copied().collect();
             let to_connect_idx = to_connect_vec.remove(self.rng.range(0, to_connect_vec.len()));
 
             let neighbors = neighbor_graph.get(&to_connect_idx).unwrap();
             let connected_neightbors: Vec<NeighBor> = neighbors
                 .iter()
                 .filter(|n| connected.contains(&n.idx))
-                .map(|n| n.clone())
+                .cloned()
                 .collect();
 
             let disconnected_neightbors: Vec<NeighBor> = neighbors
                 .iter()
                 .filter(|n| !connected.contains(&n.idx))
-                .map(|n| n.clone())
+                .cloned()
                 .collect();
 
             for disconnected in disconnected_neightbors.iter() {
@@ -226,11 +253,12 @@ impl BuildingsGenerator {
                 .get(self.rng.range(0, connected_neightbors.len()))
                 .unwrap();
 
-            let connection_wall = connection
+            let connection_wall = *connection
                 .shared_wall
                 .get(self.rng.range(0, connection.shared_wall.len()))
                 .unwrap();
-            self.place_point(*connection_wall, TileType::Door);
+            let state = self.roll_door_state(false);
+            self.place_door(connection_wall, state);
 
             to_connect.remove(&to_connect_idx);
             connected.insert(to_connect_idx);
@@ -331,7 +359,7 @@ fn expand_rect(rect: Rect, size: i32) -> Rect {
     }
 }
 
-fn scan_for_neighbors(rooms: &Vec<Rect>, building: Rect) -> HashMap<i32, Vec<NeighBor>> {
+fn scan_for_neighbors(rooms: &[Rect], building: Rect) -> HashMap<i32, Vec<NeighBor>> {
     let mut neighbors = HashMap::<i32, Vec<NeighBor>>::new();
     for (i1, room1) in rooms.iter().enumerate() {
         for (i2, room2) in rooms.iter().enumerate() {
@@ -342,15 +370,15 @@ fn scan_for_neighbors(rooms: &Vec<Rect>, building: Rect) -> HashMap<i32, Vec<Nei
                     room1.x1,
                 );
 
-                let entry1 = neighbors.entry(i1 as i32).or_insert(Vec::new());
+                let entry1 = neighbors.entry(i1 as i32).or_default();
                 entry1.push(NeighBor {
                     idx: i2 as i32,
                     shared_wall: shared_wall.clone(),
                 });
-                let entry2 = neighbors.entry(i2 as i32).or_insert(Vec::new());
+                let entry2 = neighbors.entry(i2 as i32).or_default();
                 entry2.push(NeighBor {
                     idx: i1 as i32,
-                    shared_wall: shared_wall,
+                    shared_wall,
                 });
             }
             if room2.y2 == room1.y1 && min(room1.x2, room2.x2) > max(room1.x1, room2.x1) {
@@ -360,15 +388,15 @@ fn scan_for_neighbors(rooms: &Vec<Rect>, building: Rect) -> HashMap<i32, Vec<Nei
                     room1.y1,
                 );
 
-                let entry1 = neighbors.entry(i1 as i32).or_insert(Vec::new());
+                let entry1 = neighbors.entry(i1 as i32).or_default();
                 entry1.push(NeighBor {
                     idx: i2 as i32,
                     shared_wall: shared_wall.clone(),
                 });
-                let entry2 = neighbors.entry(i2 as i32).or_insert(Vec::new());
+                let entry2 = neighbors.entry(i2 as i32).or_default();
                 entry2.push(NeighBor {
                     idx: i1 as i32,
-                    shared_wall: shared_wall,
+                    shared_wall,
                 });
             }
         }
@@ -392,12 +420,12 @@ fn scan_for_neighbors(rooms: &Vec<Rect>, building: Rect) -> HashMap<i32, Vec<Nei
                 shared_walls.append(&mut horizontal_line(room1.x1 + 1, room1.x2 - 1, room1.y2));
             }
 
-            let entry1 = neighbors.entry(i1 as i32).or_insert(Vec::new());
+            let entry1 = neighbors.entry(i1 as i32).or_default();
             entry1.push(NeighBor {
                 idx: EXT_IDX,
                 shared_wall: shared_walls.clone(),
             });
-            let entry_ext = neighbors.entry(EXT_IDX).or_insert(Vec::new());
+            let entry_ext = neighbors.entry(EXT_IDX).or_default();
             entry_ext.push(NeighBor {
                 idx: i1 as i32,
                 shared_wall: shared_walls,
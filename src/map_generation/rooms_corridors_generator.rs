@@ -2,7 +2,7 @@ use crate::{game_map::*, map::Map};
 use bracket_lib::prelude::*;
 use std::{
     cmp::{max, min},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
 };
 
 use super::MapGenerator;
@@ -62,19 +62,24 @@ impl MapGenerator for RoomsCorridorsGenerator {
             }
         }
 
-        let map = GameMap {
+        GameMap {
             tiles: self.tiles.clone(),
             revealed_tiles: vec![false; self.max_idx],
             visible_tiles: vec![false; self.max_idx],
             blocked_tiles: vec![false; self.max_idx],
             entities_tiles: vec![Vec::new(); self.max_idx],
+            ambient_light: Vec::new(),
+            light: Vec::new(),
+            night_vision_tiles: HashSet::new(),
+            smoke_tiles: HashSet::new(),
             decal_tiles: HashMap::new(),
-            rooms: rooms,
+            door_tiles: HashMap::new(),
+            tile_damage: HashMap::new(),
+            remembered_entities: HashMap::new(),
+            rooms,
             width: self.width,
             height: self.height,
-        };
-
-        map
+        }
     }
 }
 
@@ -102,7 +107,7 @@ impl RoomsCorridorsGenerator {
         for x in min(x1, x2)..=max(x1, x2) {
             let idx = self.xy_idx(Point { x, y });
             if idx > 0 && idx < self.max_idx {
-                self.tiles[idx as usize] = TileType::Floor;
+                self.tiles[idx] = TileType::Floor;
             }
         }
     }
@@ -111,7 +116,7 @@ impl RoomsCorridorsGenerator {
         for y in min(y1, y2)..=max(y1, y2) {
             let idx = self.xy_idx(Point { x, y });
             if idx > 0 && idx < self.max_idx {
-                self.tiles[idx as usize] = TileType::Floor;
+                self.tiles[idx] = TileType::Floor;
             }
         }
     }
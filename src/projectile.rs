@@ -0,0 +1,159 @@
+use bracket_lib::prelude::*;
+use specs::{prelude::*, shrev::EventChannel};
+
+use crate::{
+    components::{CombatStats, Position},
+    events::GameEvent,
+    game_map::{GameMap, TileType},
+    gamelog::GameLog,
+    map::Map,
+    systems::ParticleBuilder,
+    tile_defs::Cover,
+};
+
+const TRAIL_STEP_MS: f32 = 25.0;
+
+#[derive(Clone, Copy)]
+pub enum Impact {
+    Entity(Entity),
+    Terrain(Point),
+    Spent,
+}
+
+pub struct Shot {
+    pub impact: Impact,
+    /// Entities the projectile flew past without hitting
+    pub missed: Vec<Entity>,
+    /// Whether the hit roll landed within the best tenth of the hit chance
+    pub critical: bool,
+}
+
+/// Tiles a projectile flies through, from the shooter through the aimed tile up to its range
+pub fn line_of_fire(map: &GameMap, from: Point, to: Point, range: i32) -> Vec<Point> {
+    if from == to {
+        return Vec::new();
+    }
+    let delta = to - from;
+    let beyond = to + delta * (range / i32::max(delta.x.abs(), delta.y.abs()) + 1);
+    // Both lines include their end points
+    line2d(LineAlg::Bresenham, from, to)
+        .into_iter()
+        .skip(1)
+        .chain(line2d(LineAlg::Bresenham, to, beyond).into_iter().skip(1))
+        .take_while(|p| {
+            p.x >= 0
+                && p.x < map.width
+                && p.y >= 0
+                && p.y < map.height
+                && DistanceAlg::Pythagoras.distance2d(from, *p) <= range as f32
+        })
+        .collect()
+}
+
+/// Cover the target gets from what stands next to it on the attacker's side.
+/// Terrain covers according to its tile definition, other creatures give half cover.
+pub fn cover_from(map: &GameMap, attacker: Point, target: Point) -> Cover {
+    if DistanceAlg::Chebyshev.distance2d(attacker, target) <= 1.0 {
+        return Cover::None;
+    }
+    let delta = attacker - target;
+    let toward = Point::new(delta.x.signum(), delta.y.signum());
+    let mut neighbors = vec![target + toward];
+    if toward.x != 0 && toward.y != 0 {
+        neighbors.push(target + Point::new(toward.x, 0));
+        neighbors.push(target + Point::new(0, toward.y));
+    }
+    neighbors
+        .iter()
+        .map(|p| {
+            let idx = map.xy_idx(*p);
+            let tile = map.tiles[idx];
+            if map.blocked_tiles[idx] && tile.def().walkable && !map.is_door_closed(idx) {
+                Cover::Half
+            } else {
+                tile.def().cover
+            }
+        })
+        .max()
+        .unwrap_or(Cover::None)
+}
+
+pub fn hit_chance(accuracy: i32, cover: Cover) -> i32 {
+    i32::max(0, accuracy - cover.hit_penalty())
+}
+
+/// Walls, closed doors and furniture stop projectiles. Windows shatter and let them through.
+pub fn blocks_projectile(map: &GameMap, idx: usize) -> bool {
+    let tile = map.tiles[idx];
+    if tile == TileType::Window || tile == TileType::BrokenWindow {
+        return false;
+    }
+    map.is_door_closed(idx) || !tile.def().walkable
+}
+
+/// Flies a projectile towards the target, stopping at the first thing it hits.
+/// The aimed-at tile is hit with the given accuracy, anything else in the way with half of it,
+/// both lowered by cover.
+pub fn trace_shot(ecs: &World, shooter: Entity, target: Point, range: i32, accuracy: i32) -> Shot {
+    let mut map = ecs.write_resource::<GameMap>();
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+    let mut log = ecs.write_resource::<GameLog>();
+    let mut events = ecs.write_resource::<EventChannel<GameEvent>>();
+    let mut particle_builder = ecs.write_resource::<ParticleBuilder>();
+    let combat_stats = ecs.read_storage::<CombatStats>();
+    let origin = ecs.read_storage::<Position>().get(shooter).unwrap().pos;
+
+    let mut missed = Vec::new();
+    let mut impact = Impact::Spent;
+    let mut critical = false;
+    for (step, tile) in line_of_fire(&map, origin, target, range).iter().enumerate() {
+        particle_builder.request_delayed(
+            *tile,
+            RGB::named(YELLOW),
+            RGB::named(BLACK),
+            to_cp437('∙'),
+            TRAIL_STEP_MS * 2.0,
+            TRAIL_STEP_MS * step as f32,
+        );
+        let idx = map.xy_idx(*tile);
+
+        if map.break_window(idx) {
+            log.log("The window shatters!".to_string());
+            events.single_write(GameEvent::NoiseMade {
+                pos: *tile,
+                volume: 10,
+            });
+        }
+
+        if blocks_projectile(&map, idx) {
+            impact = Impact::Terrain(*tile);
+            break;
+        }
+
+        let base_chance = if *tile == target {
+            accuracy
+        } else {
+            accuracy / 2
+        };
+        let chance = hit_chance(base_chance, cover_from(&map, origin, *tile));
+        let victim = map.entities_tiles[idx]
+            .iter()
+            .find(|entity| **entity != shooter && combat_stats.contains(**entity))
+            .copied();
+        if let Some(victim) = victim {
+            let roll = rng.roll_dice(1, 100);
+            if roll <= chance {
+                impact = Impact::Entity(victim);
+                critical = roll <= chance / 10;
+                break;
+            }
+            missed.push(victim);
+        }
+    }
+
+    Shot {
+        impact,
+        missed,
+        critical,
+    }
+}
@@ -12,7 +12,7 @@ pub struct RandomTable<'a, T> {
 }
 
 impl<'a, T> RandomTable<'a, T> {
-    pub fn new(rng: &mut RandomNumberGenerator) -> RandomTable<T> {
+    pub fn new(rng: &mut RandomNumberGenerator) -> RandomTable<'_, T> {
         RandomTable {
             entries: Vec::new(),
             total_weight: 0,
@@ -1,4 +1,5 @@
 use bracket_lib::prelude::Point;
+use serde::{Deserialize, Serialize};
 use specs::Entity;
 
 pub struct PlayerPos {
@@ -17,6 +18,61 @@ pub enum RunState {
     MonsterTurn,
 }
 
+/// Toggles for development aids, not saved with the game
+#[derive(Default)]
+pub struct DebugOptions {
+    /// Show attack and damage rolls in the combat log
+    pub show_rolls: bool,
+}
+
+#[derive(Clone, Copy)]
+pub struct Noise {
+    pub pos: Point,
+    pub volume: i32,
+}
+
+/// Noises made during the current turn, heard by monsters within `volume` tiles
+pub struct Noises(Vec<Noise>);
+
+impl Noises {
+    pub fn new() -> Noises {
+        Noises(Vec::new())
+    }
+
+    pub fn make(&mut self, pos: Point, volume: i32) {
+        self.0.push(Noise { pos, volume });
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Noise> {
+        self.0.iter()
+    }
+}
+
+/// Tiles operators moved to during the current turn, which monsters may catch sight of
+pub struct Movements(Vec<Point>);
+
+impl Movements {
+    pub fn new() -> Movements {
+        Movements(Vec::new())
+    }
+
+    pub fn record(&mut self, pos: Point) {
+        self.0.push(pos);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Point> {
+        self.0.iter()
+    }
+}
+
 pub struct PointsOfInterest(Vec<Point>);
 
 impl PointsOfInterest {
@@ -38,14 +94,25 @@ impl PointsOfInterest {
                 return Some(self.0[(idx + 1) % self.0.len()]);
             }
         }
-        return if self.0.is_empty() {
+        if self.0.is_empty() {
             None
         } else {
             Some(self.0[0])
-        };
+        }
     }
 
     pub fn contains(&self, pos: Point) -> bool {
         self.0.contains(&pos)
     }
 }
+
+/// Running totals of the squad's deeds, shown when the run ends
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct GameStats {
+    pub kills: i32,
+    pub operators_lost: i32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub items_picked_up: i32,
+    pub doors_opened: i32,
+}
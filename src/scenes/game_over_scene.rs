@@ -1,12 +1,17 @@
 use bracket_lib::prelude::*;
 
-use crate::scenes::{Scene, SceneSignal, SceneType};
+use crate::{
+    resources::GameStats,
+    scenes::{Scene, SceneSignal, SceneType},
+};
 
-pub struct GameOverScene;
+pub struct GameOverScene {
+    pub stats: GameStats,
+}
 
 impl Scene for GameOverScene {
     fn tick(&mut self, ctx: &mut BTerm) -> SceneSignal {
-        draw(ctx);
+        draw(ctx, &self.stats);
         match read_input(ctx) {
             GameOverResult::NoSelection => SceneSignal::None,
             GameOverResult::QuitToMenu => SceneSignal::Load(SceneType::MainMenu),
@@ -27,7 +32,7 @@ fn read_input(ctx: &BTerm) -> GameOverResult {
     }
 }
 
-fn draw(ctx: &mut BTerm) {
+fn draw(ctx: &mut BTerm, stats: &GameStats) {
     ctx.cls();
     ctx.print_color_centered(
         15,
@@ -35,21 +40,20 @@ fn draw(ctx: &mut BTerm) {
         RGB::named(BLACK),
         "Your journey has ended!",
     );
-    ctx.print_color_centered(
-        17,
-        RGB::named(WHITE),
-        RGB::named(BLACK),
-        "One day, we'll tell you all about how you did.",
-    );
-    ctx.print_color_centered(
-        18,
-        RGB::named(WHITE),
-        RGB::named(BLACK),
-        "That day, sadly, is not in this chapter..",
-    );
+    let lines = [
+        format!("Hostiles killed: {}", stats.kills),
+        format!("Operators lost: {}", stats.operators_lost),
+        format!("Damage dealt: {}", stats.damage_dealt),
+        format!("Damage taken: {}", stats.damage_taken),
+        format!("Items picked up: {}", stats.items_picked_up),
+        format!("Doors opened: {}", stats.doors_opened),
+    ];
+    for (y, line) in (17..).zip(lines.iter()) {
+        ctx.print_color_centered(y, RGB::named(WHITE), RGB::named(BLACK), line);
+    }
 
     ctx.print_color_centered(
-        20,
+        24,
         RGB::named(MAGENTA),
         RGB::named(BLACK),
         "Press any key to return to the menu.",
@@ -19,12 +19,12 @@ impl<'a, 'b> Scene for GameScene<'a, 'b> {
 impl<'a, 'b> GameScene<'a, 'b> {
     pub fn new_game() -> GameScene<'a, 'b> {
         let state = init_state(MAP_WIDTH, MAP_HEIGHT);
-        GameScene { state: state }
+        GameScene { state }
     }
 
     pub fn load_game() -> GameScene<'a, 'b> {
         let mut state = init_state(MAP_WIDTH, MAP_HEIGHT);
         state.load_game();
-        GameScene { state: state }
+        GameScene { state }
     }
 }
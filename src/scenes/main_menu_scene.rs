@@ -55,7 +55,7 @@ impl MainMenuScene {
     }
 
     fn read_input(&mut self, ctx: &BTerm) -> Option<SceneType> {
-        let options = self
+        let options: Vec<_> = self
             .entries
             .iter()
             .map(|entry| (entry.label.to_string(), entry.scene))
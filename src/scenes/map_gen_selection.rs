@@ -37,22 +37,21 @@ impl Scene for MapGenSelectionScene {
 
 impl MapGenSelectionScene {
     pub fn new() -> MapGenSelectionScene {
-        let mut entries: Vec<MapGenEntry> = Vec::new();
-        entries.push(MapGenEntry {
-            map_gen_type: MapGenType::Rooms,
-            label: "Rooms and corridor",
-        });
-        entries.push(MapGenEntry {
-            map_gen_type: MapGenType::Buildings,
-            label: "Buildings",
-        });
-        MapGenSelectionScene {
-            entries,
-        }
+        let entries = vec![
+            MapGenEntry {
+                map_gen_type: MapGenType::Rooms,
+                label: "Rooms and corridor",
+            },
+            MapGenEntry {
+                map_gen_type: MapGenType::Buildings,
+                label: "Buildings",
+            },
+        ];
+        MapGenSelectionScene { entries }
     }
 
     fn read_input(&mut self, ctx: &BTerm) -> Option<MapGenType> {
-        let options = self
+        let options: Vec<_> = self
             .entries
             .iter()
             .map(|entry| (entry.label.to_string(), entry.map_gen_type))
@@ -49,13 +49,11 @@ impl MapGenTestScene {
         let mut map = match gen_type {
             MapGenType::Rooms => {
                 let mut generator = RoomsCorridorsGenerator::new(MAP_WIDTH, MAP_HEIGHT);
-                let map = generator.generate();
-                map
+                generator.generate()
             }
             MapGenType::Buildings => {
                 let mut generator = BuildingsGenerator::new(MAP_WIDTH, MAP_HEIGHT);
-                let map = generator.generate();
-                map
+                generator.generate()
             }
         };
 
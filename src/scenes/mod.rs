@@ -6,6 +6,8 @@ mod map_gen_test;
 
 use bracket_lib::prelude::GameState;
 
+use crate::resources::GameStats;
+
 use self::{
     game_over_scene::GameOverScene, game_scene::GameScene, main_menu_scene::MainMenuScene,
     map_gen_selection::{MapGenType, MapGenSelectionScene}, map_gen_test::MapGenTestScene,
@@ -16,7 +18,7 @@ pub enum SceneType {
     MainMenu,
     NewGame,
     LoadGame,
-    GameOver,
+    GameOver(GameStats),
     Quit,
     MapGenSelection,
     MapGenTest(MapGenType),
@@ -61,7 +63,7 @@ fn load_scene(scene: SceneType) -> AnyScene {
         SceneType::MainMenu => Box::new(MainMenuScene::new()),
         SceneType::NewGame => Box::new(GameScene::new_game()),
         SceneType::LoadGame => Box::new(GameScene::load_game()),
-        SceneType::GameOver => Box::new(GameOverScene {}),
+        SceneType::GameOver(stats) => Box::new(GameOverScene { stats }),
         SceneType::Quit => {
             ::std::process::exit(0);
         }
@@ -1,15 +1,43 @@
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
 
 use crate::{
+    actions::invalidate_viewsheds,
+    combat::parse_dice,
     components::*,
-    entity_containers::{EntityHashMap, EntityVec}, random_table::RandomTable,
+    entity_containers::{EntityHashMap, EntityVec},
+    game_map::GameMap,
+    identification::{
+        ItemIdentification, CONFUSION_SCROLL, FIREBALL_SCROLL, HEALTH_POTION, IDENTIFY_SCROLL,
+        MAGIC_MISSILE_SCROLL, STIM_INJECTOR, TELEPORT_SCROLL,
+    },
+    map::Map,
+    random_table::RandomTable,
+    status::StatusKind,
 };
 use bracket_lib::prelude::*;
 use specs::{saveload::*, *};
 
 const MAX_SPAWNED: i32 = 5;
+const MAX_LOOT: i32 = 2;
+const MAX_GEAR: i32 = 2;
 
-pub fn player(ecs: &mut World, pos: Point) -> Entity {
+const SQUAD: [&str; 3] = ["Alpha", "Bravo", "Charlie"];
+
+pub fn squad(ecs: &mut World, pos: Point) -> Vec<Entity> {
+    let offsets = [Point::new(0, 0), Point::new(1, 0), Point::new(-1, 0)];
+    SQUAD
+        .iter()
+        .zip(offsets.iter())
+        .map(|(name, offset)| operator(ecs, pos + *offset, name))
+        .collect()
+}
+
+fn operator(ecs: &mut World, pos: Point, name: &str) -> Entity {
+    let mut items = EntityVec::new();
+    items.push(flashlight_builder(ecs).build());
+    items.push(pistol_ammo_builder(ecs).build());
+    let mut slots = EntityHashMap::new();
+    slots.insert(EquipmentSlot::Ranged, pistol_builder(ecs).build());
     ecs.create_entity()
         .with(Position { pos })
         .with(Renderable {
@@ -20,28 +48,40 @@ pub fn player(ecs: &mut World, pos: Point) -> Entity {
         })
         .with(Player {})
         .with(Viewshed::new(20))
-        .with(Name {
-            name: "Player".to_string(),
+        .with(Facing {
+            direction: Point::new(0, -1),
         })
-        .with(CombatStats::new(30, 2, 5))
-        .with(Inventory {
-            items: EntityVec::new(),
-        })
-        .with(Equipment {
-            slots: EntityHashMap::new(),
+        .with(Name {
+            name: name.to_string(),
         })
+        .with(CombatStats::new(30, 3, 2, 0, "1d4+1"))
+        .with(Inventory { items })
+        .with(Equipment { slots })
         .marked::<SimpleMarker<SerializeMe>>()
         .build()
 }
 
 fn orc(ecs: &mut World, pos: Point) {
-    monster(ecs, pos, to_cp437('o'), "Orc");
+    let orc = monster(ecs, pos, to_cp437('o'), "Orc");
+    // Orcs carry torches
+    ecs.write_storage::<LightSource>()
+        .insert(
+            orc,
+            LightSource {
+                radius: 4,
+                color: RGB::from_f32(1.0, 0.6, 0.2),
+                cone: None,
+            },
+        )
+        .expect("Unable to insert light source");
 }
 fn goblin(ecs: &mut World, pos: Point) {
     monster(ecs, pos, to_cp437('g'), "Goblin");
 }
 
-fn monster<S: ToString>(ecs: &mut World, pos: Point, glyph: FontCharType, name: S) {
+fn monster<S: ToString>(ecs: &mut World, pos: Point, glyph: FontCharType, name: S) -> Entity {
+    let mut items = roll_loot(ecs);
+    let slots = roll_gear(ecs, &mut items);
     ecs.create_entity()
         .with(Position { pos })
         .with(Renderable {
@@ -54,18 +94,25 @@ fn monster<S: ToString>(ecs: &mut World, pos: Point, glyph: FontCharType, name:
             visible_tiles: Vec::new(),
             range: 8,
             dirty: true,
+            night_vision: false,
+            cone: Some(60.0),
+        })
+        .with(Facing {
+            direction: Point::new(0, -1),
         })
         .with(Monster {})
         .with(Name {
             name: name.to_string(),
         })
         .with(BlocksTile {})
-        .with(CombatStats::new(16, 1, 4))
+        .with(CombatStats::new(16, 2, 1, 0, "1d6"))
+        .with(Inventory { items })
+        .with(Equipment { slots })
         .marked::<SimpleMarker<SerializeMe>>()
-        .build();
+        .build()
 }
 
-pub fn spawn_room(mut ecs: &mut World, room: &Rect) {
+pub fn spawn_room(ecs: &mut World, map: &GameMap, room: &Rect) {
     let mut rng = {
         ecs.write_resource::<RandomNumberGenerator>().clone()
     };
@@ -86,8 +133,10 @@ pub fn spawn_room(mut ecs: &mut World, room: &Rect) {
                 let x = room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1));
                 let y = room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1));
                 let pos = Point { x, y };
-                if !spawn_points.contains_key(&pos) {
-                    spawn_points.insert(pos, ());
+                if !map.tiles[map.xy_idx(pos)].def().walkable {
+                    tries += 1;
+                } else if let Entry::Vacant(e) = spawn_points.entry(pos) {
+                    e.insert(());
                     added = true;
                 } else {
                     tries += 1;
@@ -99,18 +148,22 @@ pub fn spawn_room(mut ecs: &mut World, room: &Rect) {
     {
         for (spawn_pos, _u) in spawn_points.iter() {
             let spawner = spawn_table.roll();
-            spawner(&mut ecs, *spawn_pos);
+            spawner(ecs, *spawn_pos);
         }
     }
 }
 
 pub type Spawner = fn(ecs: &mut World, pos: Point);
 
-fn room_table(rng: &mut RandomNumberGenerator) -> RandomTable<Spawner> {
+fn room_table(rng: &mut RandomNumberGenerator) -> RandomTable<'_, Spawner> {
     RandomTable::<Spawner>::new(rng)
         .add(goblin, 20)
         .add(orc, 5)
         .add(health_potion, 7)
+        .add(stim_injector, 2)
+        .add(medkit, 3)
+        .add(taser, 2)
+        .add(battery_pack, 2)
         .add(fireball_scroll, 2)
         .add(confusion_scroll, 2)
         .add(magic_missile_scroll, 4)
@@ -118,41 +171,415 @@ fn room_table(rng: &mut RandomNumberGenerator) -> RandomTable<Spawner> {
         .add(shield, 3)
         .add(longsword, 2)
         .add(tower_shield, 2)
+        .add(sledgehammer, 1)
+        .add(kevlar_vest, 2)
+        .add(combat_gloves, 2)
+        .add(combat_boots, 2)
+        .add(breaching_charge, 3)
+        .add(flare, 3)
+        .add(frag_grenade, 2)
+        .add(flashbang, 2)
+        .add(smoke_grenade, 2)
+        .add(teleport_scroll, 1)
+        .add(identify_scroll, 2)
+        .add(lamp, 6)
+        .add(flashlight, 2)
+        .add(night_vision_goggles, 2)
+        .add(pistol, 2)
+        .add(rifle, 1)
+        .add(pistol_ammo, 4)
+        .add(rifle_ammo, 2)
 }
 
-fn health_potion(ecs: &mut World, pos: Point) {
+/// Colour of the item's random appearance for this run
+fn appearance_color(ecs: &World, name: &str) -> RGB {
+    ecs.read_resource::<ItemIdentification>()
+        .color(name)
+        .unwrap_or(RGB::named(WHITE))
+}
+
+type ItemBuilder = fn(ecs: &mut World) -> EntityBuilder<'_>;
+
+/// Items a monster may be carrying when spawned
+fn loot_table(rng: &mut RandomNumberGenerator) -> RandomTable<'_, Option<ItemBuilder>> {
+    RandomTable::<Option<ItemBuilder>>::new(rng)
+        .add(None, 16)
+        .add(Some(health_potion_builder), 4)
+        .add(Some(medkit_builder), 1)
+        .add(Some(stim_injector_builder), 1)
+        .add(Some(pistol_ammo_builder), 3)
+        .add(Some(dagger_builder), 1)
+}
+
+fn roll_loot(ecs: &mut World) -> EntityVec<Entity> {
+    let loot: Vec<ItemBuilder> = {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        let mut table = loot_table(&mut rng);
+        (0..MAX_LOOT).filter_map(|_| *table.roll()).collect()
+    };
+
+    let mut items = EntityVec::new();
+    for builder in loot {
+        items.push(builder(ecs).build());
+    }
+    items
+}
+
+/// Weapons and armour a monster may be wearing when spawned
+fn gear_table(rng: &mut RandomNumberGenerator) -> RandomTable<'_, Option<ItemBuilder>> {
+    RandomTable::<Option<ItemBuilder>>::new(rng)
+        .add(None, 10)
+        .add(Some(dagger_builder), 4)
+        .add(Some(longsword_builder), 2)
+        .add(Some(shield_builder), 3)
+        .add(Some(kevlar_vest_builder), 1)
+}
+
+/// Equips rolled gear, spare pieces for an occupied slot go into the inventory
+fn roll_gear(
+    ecs: &mut World,
+    items: &mut EntityVec<Entity>,
+) -> EntityHashMap<EquipmentSlot, Entity> {
+    let gear: Vec<ItemBuilder> = {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        let mut table = gear_table(&mut rng);
+        (0..MAX_GEAR).filter_map(|_| *table.roll()).collect()
+    };
+
+    let mut slots = EntityHashMap::new();
+    for builder in gear {
+        let item = builder(ecs).build();
+        let slot = ecs.read_storage::<Equippable>().get(item).unwrap().slot;
+        if let Entry::Vacant(e) = slots.entry(slot) {
+            e.insert(item);
+        } else {
+            items.push(item);
+        }
+    }
+    slots
+}
+
+fn pistol(ecs: &mut World, pos: Point) {
+    pistol_builder(ecs).with(Position { pos }).build();
+}
+
+fn pistol_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    firearm_builder(
+        ecs,
+        "Pistol",
+        1.0,
+        Firearm {
+            damage: parse_dice("1d6+2"),
+            accuracy: 75,
+            range: 10,
+            caliber: Caliber::Pistol,
+            magazine: 12,
+            ammo: 12,
+        },
+    )
+}
+
+fn rifle(ecs: &mut World, pos: Point) {
+    firearm_builder(
+        ecs,
+        "Rifle",
+        4.0,
+        Firearm {
+            damage: parse_dice("2d6+2"),
+            accuracy: 85,
+            range: 16,
+            caliber: Caliber::Rifle,
+            magazine: 30,
+            ammo: 30,
+        },
+    )
+    .with(Position { pos })
+    .build();
+}
+
+fn firearm_builder<'a>(
+    ecs: &'a mut World,
+    name: &str,
+    kg: f32,
+    firearm: Firearm,
+) -> EntityBuilder<'a> {
+    ecs.create_entity()
+        .with(Renderable {
+            glyph: to_cp437('¶'),
+            fg: RGB::named(GREY),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: name.to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg })
+        .with(Equippable {
+            slot: EquipmentSlot::Ranged,
+        })
+        .with(firearm)
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+fn pistol_ammo(ecs: &mut World, pos: Point) {
+    pistol_ammo_builder(ecs).with(Position { pos }).build();
+}
+
+fn pistol_ammo_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    ammunition_builder(ecs, "Pistol Rounds", Caliber::Pistol, 24, 0.3)
+}
+
+fn rifle_ammo(ecs: &mut World, pos: Point) {
+    ammunition_builder(ecs, "Rifle Rounds", Caliber::Rifle, 30, 0.5)
+        .with(Position { pos })
+        .build();
+}
+
+fn ammunition_builder<'a>(
+    ecs: &'a mut World,
+    name: &str,
+    caliber: Caliber,
+    rounds: i32,
+    kg: f32,
+) -> EntityBuilder<'a> {
+    ecs.create_entity()
+        .with(Renderable {
+            glyph: to_cp437('='),
+            fg: RGB::named(GOLD),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: name.to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg })
+        .with(Ammunition { caliber, rounds })
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+fn flashlight(ecs: &mut World, pos: Point) {
+    flashlight_builder(ecs).with(Position { pos }).build();
+}
+
+fn flashlight_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    ecs.create_entity()
+        .with(Renderable {
+            glyph: to_cp437('¬'),
+            fg: RGB::named(WHITE),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Flashlight".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.5 })
+        .with(Equippable {
+            slot: EquipmentSlot::Accessory,
+        })
+        .with(LightSource {
+            radius: 10,
+            color: RGB::from_f32(0.9, 0.9, 1.0),
+            cone: Some(30.0),
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+fn night_vision_goggles(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('∞'),
+            fg: RGB::named(GREEN),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Night Vision Goggles".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 1.0 })
+        .with(Equippable {
+            slot: EquipmentSlot::Head,
+        })
+        .with(NightVision {})
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn lamp(ecs: &mut World, pos: Point) {
     ecs.create_entity()
         .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('☼'),
+            fg: RGB::named(YELLOW),
+            bg: RGB::named(BLACK),
+            render_order: 3,
+        })
+        .with(Name {
+            name: "Lamp".to_string(),
+        })
+        .with(LightSource {
+            radius: 6,
+            color: RGB::from_f32(1.0, 0.9, 0.6),
+            cone: None,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn health_potion(ecs: &mut World, pos: Point) {
+    health_potion_builder(ecs).with(Position { pos }).build();
+}
+
+fn health_potion_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    let fg = appearance_color(ecs, HEALTH_POTION);
+    ecs.create_entity()
         .with(Renderable {
             glyph: to_cp437('¡'),
-            fg: RGB::named(MAGENTA),
+            fg,
             bg: RGB::named(BLACK),
             render_order: 2,
         })
         .with(Name {
-            name: "Health Potion".to_string(),
+            name: HEALTH_POTION.to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 0.5 })
         .with(Consumable {})
+        .with(Quantity { amount: 1 })
         .with(ProvidesHealing { heal_amount: 8 })
+        .with(Shatters {})
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+fn stim_injector(ecs: &mut World, pos: Point) {
+    stim_injector_builder(ecs).with(Position { pos }).build();
+}
+
+fn stim_injector_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    let fg = appearance_color(ecs, STIM_INJECTOR);
+    ecs.create_entity()
+        .with(Renderable {
+            glyph: to_cp437('¡'),
+            fg,
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: STIM_INJECTOR.to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.2 })
+        .with(Consumable {})
+        .with(Quantity { amount: 1 })
+        .with(InflictsStatus {
+            effects: vec![StatusEffect {
+                kind: StatusKind::Hasted,
+                turns: 6,
+                potency: 1,
+            }],
+        })
+        .with(Shatters {})
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+fn medkit(ecs: &mut World, pos: Point) {
+    medkit_builder(ecs).with(Position { pos }).build();
+}
+
+fn medkit_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    ecs.create_entity()
+        .with(Renderable {
+            glyph: to_cp437('+'),
+            fg: RGB::named(RED),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Medkit".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 1.0 })
+        .with(Consumable {})
+        .with(Charges {
+            charges: 3,
+            max_charges: 3,
+        })
+        .with(ProvidesHealing { heal_amount: 6 })
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+fn taser(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('¬'),
+            fg: RGB::named(YELLOW),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Taser".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.5 })
+        .with(Charges {
+            charges: 3,
+            max_charges: 3,
+        })
+        .with(Ranged { range: 4 })
+        .with(InflictsStatus {
+            effects: vec![StatusEffect {
+                kind: StatusKind::Stunned,
+                turns: 2,
+                potency: 1,
+            }],
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn battery_pack(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('='),
+            fg: RGB::named(CYAN),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Battery Pack".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.2 })
+        .with(Consumable {})
+        .with(Quantity { amount: 1 })
+        .with(Recharges {})
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 }
 
 fn magic_missile_scroll(ecs: &mut World, pos: Point) {
+    let fg = appearance_color(ecs, MAGIC_MISSILE_SCROLL);
     ecs.create_entity()
         .with(Position { pos })
         .with(Renderable {
             glyph: to_cp437(')'),
-            fg: RGB::named(CYAN),
+            fg,
             bg: RGB::named(BLACK),
             render_order: 2,
         })
         .with(Name {
-            name: "Magic Missile Scroll".to_string(),
+            name: MAGIC_MISSILE_SCROLL.to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 0.1 })
         .with(Consumable {})
+        .with(Quantity { amount: 1 })
         .with(Ranged { range: 6 })
         .with(InflictsDamage { damage: 8 })
         .marked::<SimpleMarker<SerializeMe>>()
@@ -160,19 +587,22 @@ fn magic_missile_scroll(ecs: &mut World, pos: Point) {
 }
 
 fn fireball_scroll(ecs: &mut World, pos: Point) {
+    let fg = appearance_color(ecs, FIREBALL_SCROLL);
     ecs.create_entity()
         .with(Position { pos })
         .with(Renderable {
             glyph: to_cp437(')'),
-            fg: RGB::named(ORANGE),
+            fg,
             bg: RGB::named(BLACK),
             render_order: 2,
         })
         .with(Name {
-            name: "Fireball Scroll".to_string(),
+            name: FIREBALL_SCROLL.to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 0.1 })
         .with(Consumable {})
+        .with(Quantity { amount: 1 })
         .with(Ranged { range: 6 })
         .with(InflictsDamage { damage: 20 })
         .with(AreaOfEffect { radius: 3 })
@@ -181,28 +611,269 @@ fn fireball_scroll(ecs: &mut World, pos: Point) {
 }
 
 fn confusion_scroll(ecs: &mut World, pos: Point) {
+    let fg = appearance_color(ecs, CONFUSION_SCROLL);
     ecs.create_entity()
         .with(Position { pos })
         .with(Renderable {
             glyph: to_cp437(')'),
-            fg: RGB::named(PINK),
+            fg,
             bg: RGB::named(BLACK),
             render_order: 2,
         })
         .with(Name {
-            name: "Confusion Scroll".to_string(),
+            name: CONFUSION_SCROLL.to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 0.1 })
         .with(Consumable {})
+        .with(Quantity { amount: 1 })
         .with(Ranged { range: 6 })
-        .with(Confusion { turns: 4 })
+        .with(InflictsStatus {
+            effects: vec![StatusEffect {
+                kind: StatusKind::Confused,
+                turns: 4,
+                potency: 1,
+            }],
+        })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 }
 
-fn dagger(ecs: &mut World, pos: Point) {
+fn identify_scroll(ecs: &mut World, pos: Point) {
+    let fg = appearance_color(ecs, IDENTIFY_SCROLL);
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437(')'),
+            fg,
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: IDENTIFY_SCROLL.to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.1 })
+        .with(Consumable {})
+        .with(Quantity { amount: 1 })
+        .with(Identifies {})
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn teleport_scroll(ecs: &mut World, pos: Point) {
+    let fg = appearance_color(ecs, TELEPORT_SCROLL);
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437(')'),
+            fg,
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: TELEPORT_SCROLL.to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.1 })
+        .with(Consumable {})
+        .with(Quantity { amount: 1 })
+        .with(Ranged { range: 8 })
+        .with(AreaOfEffect { radius: 0 })
+        .with(Teleports {})
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn flare(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('!'),
+            fg: RGB::named(RED),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Flare".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.3 })
+        .with(Consumable {})
+        .with(Quantity { amount: 2 })
+        .with(Ranged { range: 8 })
+        .with(AreaOfEffect { radius: 0 })
+        .with(SpawnsEntity {
+            kind: SpawnKind::BurningFlare,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn frag_grenade(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('δ'),
+            fg: RGB::named(OLIVE),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Frag Grenade".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.4 })
+        .with(Quantity { amount: 1 })
+        .with(Grenade { fuse: 2 })
+        .with(InflictsDamage { damage: 15 })
+        .with(AreaOfEffect { radius: 2 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn flashbang(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('δ'),
+            fg: RGB::named(WHITE),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Flashbang".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.3 })
+        .with(Quantity { amount: 1 })
+        .with(Grenade { fuse: 1 })
+        .with(AreaOfEffect { radius: 3 })
+        .with(InflictsStatus {
+            effects: vec![
+                StatusEffect {
+                    kind: StatusKind::Blinded,
+                    turns: 3,
+                    potency: 1,
+                },
+                StatusEffect {
+                    kind: StatusKind::Stunned,
+                    turns: 2,
+                    potency: 1,
+                },
+            ],
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn smoke_grenade(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('δ'),
+            fg: RGB::named(GREY),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Smoke Grenade".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.4 })
+        .with(Quantity { amount: 1 })
+        .with(Grenade { fuse: 1 })
+        .with(AreaOfEffect { radius: 2 })
+        .with(SpawnsEntity {
+            kind: SpawnKind::SmokeCloud,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+pub fn spawn_kind(ecs: &mut World, kind: SpawnKind, pos: Point) {
+    match kind {
+        SpawnKind::BurningFlare => burning_flare(ecs, pos),
+        SpawnKind::SmokeCloud => smoke_cloud(ecs, pos),
+    }
+}
+
+fn smoke_cloud(ecs: &mut World, pos: Point) {
+    {
+        let mut map = ecs.write_resource::<GameMap>();
+        let idx = map.xy_idx(pos);
+        // Clouds do not pile up, nor seep into walls
+        if !map.tiles[idx].def().walkable || !map.smoke_tiles.insert(idx) {
+            return;
+        }
+    }
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('▒'),
+            fg: RGB::named(GREY),
+            bg: RGB::named(BLACK),
+            render_order: 0,
+        })
+        .with(Name {
+            name: "Smoke".to_string(),
+        })
+        .with(Smoke { turns: 6 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+    invalidate_viewsheds(ecs);
+}
+
+fn burning_flare(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('☼'),
+            fg: RGB::named(RED),
+            bg: RGB::named(BLACK),
+            render_order: 3,
+        })
+        .with(Name {
+            name: "Burning Flare".to_string(),
+        })
+        .with(LightSource {
+            radius: 5,
+            color: RGB::from_f32(1.0, 0.3, 0.2),
+            cone: None,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn breaching_charge(ecs: &mut World, pos: Point) {
     ecs.create_entity()
         .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('δ'),
+            fg: RGB::named(ORANGE),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Breaching Charge".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 2.0 })
+        .with(Consumable {})
+        .with(Quantity { amount: 1 })
+        .with(Ranged { range: 1 })
+        .with(InflictsDamage { damage: 20 })
+        .with(AreaOfEffect { radius: 0 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn dagger(ecs: &mut World, pos: Point) {
+    dagger_builder(ecs).with(Position { pos }).build();
+}
+
+fn dagger_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    ecs.create_entity()
         .with(Renderable {
             glyph: to_cp437('/'),
             fg: RGB::named(CYAN),
@@ -213,17 +884,23 @@ fn dagger(ecs: &mut World, pos: Point) {
             name: "Dagger".to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 0.5 })
         .with(Equippable {
             slot: EquipmentSlot::Melee,
         })
-        .with(MeleePowerBonus { power: 2 })
+        .with(MeleeWeapon {
+            damage: parse_dice("1d4+1"),
+            hit_bonus: 2,
+        })
         .marked::<SimpleMarker<SerializeMe>>()
-        .build();
 }
 
 fn shield(ecs: &mut World, pos: Point) {
+    shield_builder(ecs).with(Position { pos }).build();
+}
+
+fn shield_builder(ecs: &mut World) -> EntityBuilder<'_> {
     ecs.create_entity()
-        .with(Position { pos })
         .with(Renderable {
             glyph: to_cp437('('),
             fg: RGB::named(CYAN),
@@ -234,17 +911,20 @@ fn shield(ecs: &mut World, pos: Point) {
             name: "Shield".to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 5.0 })
         .with(Equippable {
             slot: EquipmentSlot::Shield,
         })
-        .with(DefenseBonus { defense: 1 })
+        .with(DefenseBonus { dodge: 2, armor: 0 })
         .marked::<SimpleMarker<SerializeMe>>()
-        .build();
 }
 
 fn longsword(ecs: &mut World, pos: Point) {
+    longsword_builder(ecs).with(Position { pos }).build();
+}
+
+fn longsword_builder(ecs: &mut World) -> EntityBuilder<'_> {
     ecs.create_entity()
-        .with(Position { pos })
         .with(Renderable {
             glyph: to_cp437('/'),
             fg: RGB::named(YELLOW),
@@ -255,12 +935,15 @@ fn longsword(ecs: &mut World, pos: Point) {
             name: "Longsword".to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 3.0 })
         .with(Equippable {
             slot: EquipmentSlot::Melee,
         })
-        .with(MeleePowerBonus { power: 4 })
+        .with(MeleeWeapon {
+            damage: parse_dice("1d8+1"),
+            hit_bonus: 0,
+        })
         .marked::<SimpleMarker<SerializeMe>>()
-        .build();
 }
 
 fn tower_shield(ecs: &mut World, pos: Point) {
@@ -276,10 +959,105 @@ fn tower_shield(ecs: &mut World, pos: Point) {
             name: "Tower Shield".to_string(),
         })
         .with(Item {})
+        .with(Weight { kg: 10.0 })
         .with(Equippable {
             slot: EquipmentSlot::Shield,
         })
-        .with(DefenseBonus { defense: 3 })
+        .with(DefenseBonus { dodge: 1, armor: 2 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn sledgehammer(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('/'),
+            fg: RGB::named(ORANGE),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Sledgehammer".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 6.0 })
+        .with(Equippable {
+            slot: EquipmentSlot::Melee,
+        })
+        .with(TwoHanded {})
+        .with(MeleeWeapon {
+            damage: parse_dice("2d6+1"),
+            hit_bonus: -1,
+        })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn kevlar_vest(ecs: &mut World, pos: Point) {
+    kevlar_vest_builder(ecs).with(Position { pos }).build();
+}
+
+fn kevlar_vest_builder(ecs: &mut World) -> EntityBuilder<'_> {
+    ecs.create_entity()
+        .with(Renderable {
+            glyph: to_cp437('['),
+            fg: RGB::named(GREEN),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Kevlar Vest".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 4.0 })
+        .with(Equippable {
+            slot: EquipmentSlot::Body,
+        })
+        .with(DefenseBonus { dodge: 0, armor: 2 })
+        .marked::<SimpleMarker<SerializeMe>>()
+}
+
+fn combat_gloves(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('['),
+            fg: RGB::named(BROWN1),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Combat Gloves".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 0.3 })
+        .with(Equippable {
+            slot: EquipmentSlot::Hands,
+        })
+        .with(DefenseBonus { dodge: 0, armor: 1 })
+        .marked::<SimpleMarker<SerializeMe>>()
+        .build();
+}
+
+fn combat_boots(ecs: &mut World, pos: Point) {
+    ecs.create_entity()
+        .with(Position { pos })
+        .with(Renderable {
+            glyph: to_cp437('['),
+            fg: RGB::named(GREY),
+            bg: RGB::named(BLACK),
+            render_order: 2,
+        })
+        .with(Name {
+            name: "Combat Boots".to_string(),
+        })
+        .with(Item {})
+        .with(Weight { kg: 1.5 })
+        .with(Equippable {
+            slot: EquipmentSlot::Feet,
+        })
+        .with(DefenseBonus { dodge: 1, armor: 0 })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 }
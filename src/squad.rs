@@ -0,0 +1,74 @@
+use specs::prelude::*;
+
+use crate::{
+    actions::{Action, BusyAction},
+    components::{Busy, Player, Position, Viewshed},
+    resources::{PlayerEntity, PlayerPos},
+};
+
+pub fn operators(ecs: &World) -> Vec<Entity> {
+    let entities = ecs.entities();
+    let players = ecs.read_storage::<Player>();
+    let positions = ecs.read_storage::<Position>();
+    (&entities, &players, &positions)
+        .join()
+        .map(|(entity, _player, _pos)| entity)
+        .collect()
+}
+
+pub fn is_squad_dead(ecs: &World) -> bool {
+    operators(ecs).is_empty()
+}
+
+/// Hands control to the operator following the active one. Dead operators are skipped.
+pub fn cycle_operator(ecs: &World) {
+    let operators = operators(ecs);
+    if operators.is_empty() {
+        return;
+    }
+
+    let active = ecs.read_resource::<PlayerEntity>().entity;
+    let next = operators
+        .iter()
+        .find(|operator| operator.id() > active.id())
+        .unwrap_or(&operators[0]);
+    select_operator(ecs, *next);
+}
+
+/// Makes sure the active operator is still alive, handing control to the next one otherwise.
+pub fn ensure_active_operator(ecs: &World) {
+    let active = ecs.read_resource::<PlayerEntity>().entity;
+    if !ecs.entities().is_alive(active) {
+        cycle_operator(ecs);
+    }
+}
+
+/// Counts down the rounds the other operators are busy for.
+/// The active operator spends them in place of acting instead.
+pub fn tick_busy_operators(ecs: &mut World) {
+    let active = ecs.read_resource::<PlayerEntity>().entity;
+    let busy: Vec<Entity> = {
+        let busy = ecs.read_storage::<Busy>();
+        operators(ecs)
+            .into_iter()
+            .filter(|operator| *operator != active && busy.contains(*operator))
+            .collect()
+    };
+    for operator in busy {
+        BusyAction {}.run(operator, ecs);
+    }
+}
+
+fn select_operator(ecs: &World, operator: Entity) {
+    let positions = ecs.read_storage::<Position>();
+    let pos = positions.get(operator).unwrap().pos;
+
+    ecs.write_resource::<PlayerEntity>().entity = operator;
+    ecs.write_resource::<PlayerPos>().pos = pos;
+
+    // Shared vision has to be rebuilt when a downed operator stops seeing
+    let mut viewsheds = ecs.write_storage::<Viewshed>();
+    if let Some(viewshed) = viewsheds.get_mut(operator) {
+        viewshed.dirty = true;
+    }
+}
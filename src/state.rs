@@ -2,13 +2,16 @@ use bracket_lib::prelude::*;
 use specs::{prelude::*, saveload::*};
 
 use crate::{
+    actions::{has_component, Action, BusyAction},
     components::*,
     game_display::{GameDisplay, GameSignal},
     gamelog::GameLog,
+    identification::ItemIdentification,
     map_generation::{self, MapGenerator},
     resources::*,
     scenes::{Scene, SceneSignal, SceneType},
-    spawner,
+    spawner, squad,
+    status::{self, StatusKind},
     systems::*,
 };
 
@@ -18,22 +21,19 @@ pub struct State<'a, 'b> {
     indexing_systems: Dispatcher<'a, 'b>,
     runstate: RunState,
     display: GameDisplay,
+    /// Whether a hasted player already took its extra action this turn
+    extra_action_taken: bool,
 }
 
 impl<'a, 'b> State<'a, 'b> {
     fn run_systems(&mut self) {
+        run_effects_queue(&mut self.ecs);
         self.gameplay_systems.dispatch(&self.ecs);
         self.ecs.maintain();
         self.indexing_systems.dispatch(&self.ecs);
         self.ecs.maintain();
     }
 
-    fn is_player_dead(&mut self) -> bool {
-        let entities = self.ecs.entities();
-        let player_entity = self.ecs.read_resource::<PlayerEntity>().entity;
-        !entities.is_alive(player_entity)
-    }
-
     pub fn load_game(&mut self) {
         load_game(&mut self.ecs);
     }
@@ -41,9 +41,11 @@ impl<'a, 'b> State<'a, 'b> {
 
 impl<'a, 'b> Scene for State<'a, 'b> {
     fn tick(&mut self, ctx: &mut BTerm) -> SceneSignal {
-        if self.is_player_dead() {
-            return SceneSignal::Load(SceneType::GameOver);
+        if squad::is_squad_dead(&self.ecs) {
+            let stats = *self.ecs.read_resource::<GameStats>();
+            return SceneSignal::Load(SceneType::GameOver(stats));
         }
+        squad::ensure_active_operator(&self.ecs);
 
         particle_system::cull_dead_particles(&mut self.ecs, ctx);
 
@@ -56,6 +58,22 @@ impl<'a, 'b> Scene for State<'a, 'b> {
             }
             RunState::AwaitingInput => {
                 self.run_systems();
+                let player_entity = self.ecs.read_resource::<PlayerEntity>().entity;
+                if has_component::<Busy>(&self.ecs, player_entity) {
+                    BusyAction {}.run(player_entity, &mut self.ecs);
+                    self.runstate = RunState::PlayerTurn;
+                    return SceneSignal::None;
+                }
+                if status::loses_turn(&self.ecs, player_entity) {
+                    let names = self.ecs.read_storage::<Name>();
+                    let mut log = self.ecs.write_resource::<GameLog>();
+                    log.log(format!(
+                        "{} is unable to act.",
+                        names.get(player_entity).unwrap().name
+                    ));
+                    self.runstate = RunState::PlayerTurn;
+                    return SceneSignal::None;
+                }
                 match self.display.read_input(&self.ecs, ctx) {
                     GameSignal::None => {}
                     GameSignal::Perform(action) => {
@@ -71,11 +89,25 @@ impl<'a, 'b> Scene for State<'a, 'b> {
             }
             RunState::PlayerTurn => {
                 self.run_systems();
-                self.runstate = RunState::MonsterTurn;
+                let player_entity = self.ecs.read_resource::<PlayerEntity>().entity;
+                if !self.extra_action_taken
+                    && status::has_status(&self.ecs, player_entity, StatusKind::Hasted)
+                {
+                    self.extra_action_taken = true;
+                    self.runstate = RunState::AwaitingInput;
+                } else {
+                    self.extra_action_taken = false;
+                    self.runstate = RunState::MonsterTurn;
+                }
             }
             RunState::MonsterTurn => {
+                burn_fuses(&mut self.ecs);
+                squad::tick_busy_operators(&mut self.ecs);
                 self.run_systems();
                 run_monster_ai(&mut self.ecs);
+                StatusSystem {}.run_now(&self.ecs);
+                SmokeSystem {}.run_now(&self.ecs);
+                self.ecs.maintain();
                 self.runstate = RunState::AwaitingInput;
             }
         }
@@ -95,17 +127,38 @@ pub fn init_state<'a, 'b>(width: i32, height: i32) -> State<'a, 'b> {
 
     world.register::<Renderable>();
     world.register::<Item>();
+    world.register::<Corpse>();
     world.register::<ProvidesHealing>();
     world.register::<Ranged>();
     world.register::<InflictsDamage>();
     world.register::<Consumable>();
+    world.register::<Quantity>();
+    world.register::<Charges>();
+    world.register::<Recharges>();
+    world.register::<Weight>();
     world.register::<Monster>();
     world.register::<Equippable>();
-    world.register::<MeleePowerBonus>();
+    world.register::<TwoHanded>();
+    world.register::<MeleeWeapon>();
     world.register::<AreaOfEffect>();
     world.register::<DefenseBonus>();
-    world.register::<Confused>();
-    world.register::<Confusion>();
+    world.register::<StatusEffects>();
+    world.register::<InflictsStatus>();
+    world.register::<Smoke>();
+    world.register::<Grenade>();
+    world.register::<LitFuse>();
+    world.register::<Shatters>();
+    world.register::<SpawnsEntity>();
+    world.register::<Teleports>();
+    world.register::<Identifies>();
+    world.register::<Busy>();
+    world.register::<Vaulting>();
+    world.register::<Investigating>();
+    world.register::<LightSource>();
+    world.register::<Facing>();
+    world.register::<NightVision>();
+    world.register::<Firearm>();
+    world.register::<Ammunition>();
     world.register::<Inventory>();
     world.register::<Equipment>();
 
@@ -113,6 +166,7 @@ pub fn init_state<'a, 'b>(width: i32, height: i32) -> State<'a, 'b> {
     world.register::<SerializationHelper>();
     world.insert(SimpleMarkerAllocator::<SerializeMe>::new());
     world.insert(particle_system::ParticleBuilder::new());
+    world.insert(EffectQueue::new());
 
     let mut gs = State {
         ecs: world,
@@ -120,19 +174,22 @@ pub fn init_state<'a, 'b>(width: i32, height: i32) -> State<'a, 'b> {
         indexing_systems: indexing_dispatcher,
         runstate: RunState::PreRun,
         display: GameDisplay::new(),
+        extra_action_taken: false,
     };
 
-    gs.ecs.insert(RandomNumberGenerator::new());
+    let mut rng = RandomNumberGenerator::new();
+    gs.ecs.insert(ItemIdentification::new(&mut rng));
+    gs.ecs.insert(rng);
 
     let mut generator =
         map_generation::buildings_generator::BuildingsGenerator::new(width, height);
     let map = generator.generate();
 
     let room_center = map.rooms[0].center();
-    let player_entity = spawner::player(&mut gs.ecs, room_center);
+    let operators = spawner::squad(&mut gs.ecs, room_center);
 
     for room in map.rooms.iter().skip(1) {
-        spawner::spawn_room(&mut gs.ecs, room);
+        spawner::spawn_room(&mut gs.ecs, &map, room);
     }
 
     gs.ecs.insert(map);
@@ -141,12 +198,15 @@ pub fn init_state<'a, 'b>(width: i32, height: i32) -> State<'a, 'b> {
     });
 
     gs.ecs.insert(PlayerEntity {
-        entity: player_entity,
+        entity: operators[0],
     });
 
     gs.ecs.insert(PlayerPos { pos: room_center });
 
     gs.ecs.insert(PointsOfInterest::new());
+    gs.ecs.insert(Noises::new());
+    gs.ecs.insert(Movements::new());
+    gs.ecs.insert(DebugOptions::default());
 
     gs
 }
@@ -0,0 +1,109 @@
+use bracket_lib::prelude::*;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+
+use crate::components::{StatusEffects, Viewshed};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusKind {
+    /// Stumbles around when moving
+    Confused,
+    /// Loses its turns
+    Stunned,
+    /// Loses `potency` hp every turn
+    Bleeding,
+    /// Loses `potency` hp every turn
+    Poisoned,
+    /// Only sees adjacent tiles
+    Blinded,
+    /// Loses every other turn
+    Slowed,
+    /// Acts twice per turn
+    Hasted,
+    /// Keeping its head down, shoots and strikes poorly
+    Suppressed,
+}
+
+/// How a new application of an effect combines with one already running
+pub enum Stacking {
+    /// Keeps the longest duration
+    Refresh,
+    /// Adds up the durations
+    Extend,
+    /// Keeps the longest duration and adds up the potency
+    Intensify,
+}
+
+impl StatusKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusKind::Confused => "confused",
+            StatusKind::Stunned => "stunned",
+            StatusKind::Bleeding => "bleeding",
+            StatusKind::Poisoned => "poisoned",
+            StatusKind::Blinded => "blinded",
+            StatusKind::Slowed => "slowed",
+            StatusKind::Hasted => "hasted",
+            StatusKind::Suppressed => "suppressed",
+        }
+    }
+
+    pub fn icon(&self) -> (FontCharType, RGB) {
+        let (glyph, color) = match self {
+            StatusKind::Confused => ('?', MAGENTA),
+            StatusKind::Stunned => ('*', YELLOW),
+            StatusKind::Bleeding => ('‼', RED),
+            StatusKind::Poisoned => ('♣', GREEN),
+            StatusKind::Blinded => ('○', GREY),
+            StatusKind::Slowed => ('▼', BLUE),
+            StatusKind::Hasted => ('▲', CYAN),
+            StatusKind::Suppressed => ('!', ORANGE),
+        };
+        (to_cp437(glyph), RGB::named(color))
+    }
+
+    pub fn stacking(&self) -> Stacking {
+        match self {
+            StatusKind::Bleeding => Stacking::Intensify,
+            StatusKind::Poisoned => Stacking::Extend,
+            _ => Stacking::Refresh,
+        }
+    }
+
+    /// Effect cancelled out when this one is applied
+    pub fn opposite(&self) -> Option<StatusKind> {
+        match self {
+            StatusKind::Slowed => Some(StatusKind::Hasted),
+            StatusKind::Hasted => Some(StatusKind::Slowed),
+            _ => None,
+        }
+    }
+}
+
+/// Puts the effect on the target, stacking it with any instance already running
+pub fn apply_status(ecs: &World, target: Entity, kind: StatusKind, turns: i32, potency: i32) {
+    let mut storage = ecs.write_storage::<StatusEffects>();
+    let statuses = storage
+        .entry(target)
+        .expect("Unable to access status effects")
+        .or_insert_with(StatusEffects::default);
+    statuses.apply(kind, turns, potency);
+
+    if kind == StatusKind::Blinded {
+        if let Some(viewshed) = ecs.write_storage::<Viewshed>().get_mut(target) {
+            viewshed.dirty = true;
+        }
+    }
+}
+
+pub fn has_status(ecs: &World, entity: Entity, kind: StatusKind) -> bool {
+    ecs.read_storage::<StatusEffects>()
+        .get(entity)
+        .is_some_and(|statuses| statuses.has(kind))
+}
+
+pub fn loses_turn(ecs: &World, entity: Entity) -> bool {
+    ecs.read_storage::<StatusEffects>()
+        .get(entity)
+        .is_some_and(|statuses| statuses.loses_turn())
+}
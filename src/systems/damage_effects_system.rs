@@ -0,0 +1,58 @@
+use bracket_lib::prelude::*;
+use specs::{
+    prelude::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    events::{register_reader, GameEvent},
+    game_map::{Decal, GameMap},
+    map::Map,
+};
+
+use super::ParticleBuilder;
+
+/// Splatters blood around wounded and dead creatures
+#[derive(Default)]
+pub struct DamageEffectsSystem {
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'a> System<'a> for DamageEffectsSystem {
+    type SystemData = (
+        Read<'a, EventChannel<GameEvent>>,
+        WriteExpect<'a, GameMap>,
+        WriteExpect<'a, ParticleBuilder>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(register_reader(world));
+    }
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (events, mut map, mut particle_builder) = data;
+        for event in events.read(self.reader.as_mut().unwrap()) {
+            match event {
+                GameEvent::EntityDamaged { pos, bleeds, .. } => {
+                    if *bleeds {
+                        let idx = map.xy_idx(*pos);
+                        map.decal_tiles.insert(idx, Decal::blood());
+                    }
+                    particle_builder.request(
+                        *pos,
+                        RGB::named(RED),
+                        RGB::named(BLACK),
+                        to_cp437('‼'),
+                        200.0,
+                    );
+                }
+                GameEvent::EntityDied { pos, .. } => {
+                    let idx = map.xy_idx(*pos);
+                    map.decal_tiles.insert(idx, Decal::blood());
+                }
+                _ => {}
+            }
+        }
+    }
+}
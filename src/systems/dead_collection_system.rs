@@ -1,5 +1,6 @@
-use crate::{components::*, gamelog::GameLog};
-use specs::prelude::*;
+use crate::{components::*, events::GameEvent};
+use bracket_lib::prelude::*;
+use specs::{prelude::*, saveload::*, shrev::EventChannel};
 
 pub struct DeadCollection {}
 
@@ -7,34 +8,87 @@ impl<'a> System<'a> for DeadCollection {
     type SystemData = (
         ReadStorage<'a, CombatStats>,
         ReadStorage<'a, Player>,
-        ReadStorage<'a, Name>,
-        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, Name>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, Renderable>,
+        WriteStorage<'a, Corpse>,
+        ReadStorage<'a, Inventory>,
+        ReadStorage<'a, Equipment>,
+        WriteStorage<'a, SimpleMarker<SerializeMe>>,
+        Write<'a, SimpleMarkerAllocator<SerializeMe>>,
+        Write<'a, EventChannel<GameEvent>>,
         Entities<'a>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (combat_stats, players, names, mut logs, entities) = data;
-        let mut dead: Vec<Entity> = Vec::new();
+        let (
+            combat_stats,
+            players,
+            mut names,
+            mut positions,
+            mut renderables,
+            mut corpses,
+            inventories,
+            equipments,
+            mut markers,
+            mut allocator,
+            mut events,
+            entities,
+        ) = data;
+        let mut dead: Vec<(Entity, Point)> = Vec::new();
 
-        for (entity, stats) in (&entities, &combat_stats).join() {
+        for (entity, stats, pos) in (&entities, &combat_stats, &positions).join() {
             if stats.hp < 1 {
-                let player = players.get(entity);
-                match player {
-                    None => {
-                        let victim_name = names.get(entity);
-                        if let Some(victim_name) = victim_name {
-                            logs.log(format!("{} is dead", &victim_name.name));
-                        }
-                    }
-                    Some(_) => {
-                        logs.log("You die".to_string());
-                    }
+                let victim_name = names.get(entity);
+                if let Some(victim_name) = victim_name {
+                    events.single_write(GameEvent::EntityDied {
+                        name: victim_name.name.clone(),
+                        pos: pos.pos,
+                        was_player: players.contains(entity),
+                    });
                 }
-                dead.push(entity);
+                dead.push((entity, pos.pos));
             }
         }
 
-        for victim in dead {
+        for (victim, pos) in dead {
+            // Everything the victim carried falls to the ground
+            let mut dropped: Vec<Entity> = Vec::new();
+            if let Some(inventory) = inventories.get(victim) {
+                dropped.extend(inventory.items.iter());
+            }
+            if let Some(equipment) = equipments.get(victim) {
+                dropped.extend(equipment.slots.values());
+            }
+            for item in dropped {
+                positions
+                    .insert(item, Position { pos })
+                    .expect("Unable to insert position");
+            }
+
+            let corpse_name = names
+                .get(victim)
+                .map_or("Body".to_string(), |name| format!("{} corpse", name.name));
+            let fg = renderables
+                .get(victim)
+                .map_or(RGB::named(GREY), |render| render.fg);
+            entities
+                .build_entity()
+                .with(Position { pos }, &mut positions)
+                .with(
+                    Renderable {
+                        glyph: to_cp437('%'),
+                        fg,
+                        bg: RGB::named(BLACK),
+                        render_order: 3,
+                    },
+                    &mut renderables,
+                )
+                .with(Name { name: corpse_name }, &mut names)
+                .with(Corpse {}, &mut corpses)
+                .marked(&mut markers, &mut allocator)
+                .build();
+
             entities.delete(victim).expect("Unable to delete");
         }
     }
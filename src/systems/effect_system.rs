@@ -0,0 +1,307 @@
+use bracket_lib::prelude::*;
+use specs::prelude::*;
+
+use crate::{
+    actions::invalidate_viewsheds,
+    components::*,
+    events::{publish, GameEvent},
+    game_map::GameMap,
+    gamelog::GameLog,
+    identification::{identify_carried, item_name},
+    map::Map,
+    resources::{PlayerEntity, PlayerPos},
+    spawner,
+    status::{apply_status, StatusKind},
+};
+
+use super::ParticleBuilder;
+
+#[derive(Clone)]
+pub enum EffectType {
+    Damage {
+        amount: i32,
+    },
+    Healing {
+        amount: i32,
+    },
+    Status {
+        kind: StatusKind,
+        turns: i32,
+        potency: i32,
+    },
+    /// Damages walls, doors and windows
+    TerrainDamage {
+        amount: i32,
+    },
+    Particle {
+        glyph: FontCharType,
+        fg: RGB,
+        lifetime: f32,
+    },
+    SpawnEntity {
+        kind: SpawnKind,
+    },
+    Teleport {
+        destination: Point,
+    },
+    /// Reveals what the carried items are
+    Identify,
+    /// Refills the carried rechargeable devices
+    Recharge,
+}
+
+/// What an effect applies to. Creature effects hit the entities found there,
+/// terrain effects the tiles they stand on.
+#[derive(Clone)]
+pub enum Targets {
+    Single { target: Entity },
+    Tile { tile: Point },
+    Area { tiles: Vec<Point> },
+    Entities { targets: Vec<Entity> },
+}
+
+pub struct EffectRequest {
+    pub effect: EffectType,
+    pub targets: Targets,
+}
+
+/// Effects waiting to be resolved at the start of the next systems run
+pub struct EffectQueue {
+    requests: Vec<EffectRequest>,
+}
+
+impl EffectQueue {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> EffectQueue {
+        EffectQueue {
+            requests: Vec::new(),
+        }
+    }
+}
+
+pub fn add_effect(ecs: &World, effect: EffectType, targets: Targets) {
+    ecs.write_resource::<EffectQueue>()
+        .requests
+        .push(EffectRequest { effect, targets });
+}
+
+pub fn run_effects_queue(ecs: &mut World) {
+    // Resolving an effect may queue new ones
+    loop {
+        let requests = std::mem::take(&mut ecs.write_resource::<EffectQueue>().requests);
+        if requests.is_empty() {
+            break;
+        }
+        for request in requests.iter() {
+            resolve(ecs, request);
+        }
+    }
+}
+
+fn resolve(ecs: &mut World, request: &EffectRequest) {
+    match &request.effect {
+        EffectType::TerrainDamage { amount } => {
+            damage_terrain(ecs, &target_tiles(ecs, &request.targets), *amount)
+        }
+        EffectType::Particle {
+            glyph,
+            fg,
+            lifetime,
+        } => {
+            let mut particle_builder = ecs.write_resource::<ParticleBuilder>();
+            for tile in target_tiles(ecs, &request.targets) {
+                particle_builder.request(tile, *fg, RGB::named(BLACK), *glyph, *lifetime);
+            }
+        }
+        EffectType::SpawnEntity { kind } => {
+            for tile in target_tiles(ecs, &request.targets) {
+                spawner::spawn_kind(ecs, *kind, tile);
+            }
+        }
+        EffectType::Recharge => {
+            for target in target_entities(ecs, &request.targets) {
+                recharge(ecs, target);
+            }
+        }
+        EffectType::Identify => {
+            for target in target_entities(ecs, &request.targets) {
+                identify_carried(ecs, target);
+            }
+        }
+        effect => {
+            for target in target_entities(ecs, &request.targets) {
+                affect_entity(ecs, effect, target);
+            }
+        }
+    }
+}
+
+fn target_entities(ecs: &World, targets: &Targets) -> Vec<Entity> {
+    let map = ecs.read_resource::<GameMap>();
+    let entities_at = |tile: &Point| map.entities_tiles[map.xy_idx(*tile)].clone();
+    match targets {
+        Targets::Single { target } => vec![*target],
+        Targets::Tile { tile } => entities_at(tile),
+        Targets::Area { tiles } => tiles.iter().flat_map(entities_at).collect(),
+        Targets::Entities { targets } => targets.clone(),
+    }
+}
+
+fn target_tiles(ecs: &World, targets: &Targets) -> Vec<Point> {
+    let positions = ecs.read_storage::<Position>();
+    match targets {
+        Targets::Single { target } => positions.get(*target).map(|p| p.pos).into_iter().collect(),
+        Targets::Tile { tile } => vec![*tile],
+        Targets::Area { tiles } => tiles.clone(),
+        Targets::Entities { targets } => targets
+            .iter()
+            .filter_map(|target| positions.get(*target).map(|p| p.pos))
+            .collect(),
+    }
+}
+
+fn affect_entity(ecs: &mut World, effect: &EffectType, target: Entity) {
+    let pos = match ecs.read_storage::<Position>().get(target) {
+        None => return,
+        Some(pos) => pos.pos,
+    };
+    if !ecs.read_storage::<CombatStats>().contains(target) {
+        return;
+    }
+    let name = ecs.read_storage::<Name>().get(target).unwrap().name.clone();
+    let seen = {
+        let map = ecs.read_resource::<GameMap>();
+        map.visible_tiles[map.xy_idx(pos)]
+    };
+
+    let (message, glyph, fg) = match effect {
+        EffectType::Damage { amount } => {
+            let mut combat_stats = ecs.write_storage::<CombatStats>();
+            combat_stats.get_mut(target).unwrap().deal_damage(*amount);
+            publish(
+                ecs,
+                GameEvent::EntityDamaged {
+                    target,
+                    pos,
+                    amount: *amount,
+                    bleeds: true,
+                },
+            );
+            if seen {
+                ecs.write_resource::<GameLog>()
+                    .log(format!("{} takes {} hp.", name, amount));
+            }
+            return;
+        }
+        EffectType::Healing { amount } => {
+            let mut combat_stats = ecs.write_storage::<CombatStats>();
+            combat_stats.get_mut(target).unwrap().heal(*amount);
+            (
+                format!("{} heals {} hp.", name, amount),
+                to_cp437('♥'),
+                RGB::named(GREEN),
+            )
+        }
+        EffectType::Status {
+            kind,
+            turns,
+            potency,
+        } => {
+            apply_status(ecs, target, *kind, *turns, *potency);
+            let (glyph, fg) = kind.icon();
+            (format!("{} is {}.", name, kind.label()), glyph, fg)
+        }
+        EffectType::Teleport { destination } => {
+            if teleport(ecs, target, *destination) {
+                (
+                    format!("{} vanishes in a flash.", name),
+                    to_cp437('☼'),
+                    RGB::named(CYAN),
+                )
+            } else {
+                // The destination is taken, so the teleport is wasted
+                (
+                    format!("The teleport fizzles around {}.", name),
+                    to_cp437('☼'),
+                    RGB::named(GREY),
+                )
+            }
+        }
+        _ => return,
+    };
+
+    let mut particle_builder = ecs.write_resource::<ParticleBuilder>();
+    particle_builder.request(pos, fg, RGB::named(BLACK), glyph, 200.0);
+    if seen {
+        ecs.write_resource::<GameLog>().log(message);
+    }
+}
+
+/// Refills every device the owner carries that isn't used up when empty
+fn recharge(ecs: &World, owner: Entity) {
+    let inventories = ecs.read_storage::<Inventory>();
+    let consumables = ecs.read_storage::<Consumable>();
+    let mut charges = ecs.write_storage::<Charges>();
+    let items = match inventories.get(owner) {
+        None => return,
+        Some(inventory) => inventory.items.iter().copied(),
+    };
+
+    let mut recharged = Vec::new();
+    for item in items.filter(|item| !consumables.contains(*item)) {
+        if let Some(device) = charges.get_mut(item) {
+            if device.charges < device.max_charges {
+                device.charges = device.max_charges;
+                recharged.push(item);
+            }
+        }
+    }
+
+    if ecs.read_storage::<Player>().contains(owner) {
+        let mut log = ecs.write_resource::<GameLog>();
+        for item in recharged {
+            log.log(format!("The {} is fully charged.", item_name(ecs, item)));
+        }
+    }
+}
+
+fn damage_terrain(ecs: &mut World, tiles: &[Point], amount: i32) {
+    let mut destroyed = false;
+    {
+        let mut map = ecs.write_resource::<GameMap>();
+        for tile in tiles.iter() {
+            let idx = map.xy_idx(*tile);
+            if map.damage_tile(idx, amount) {
+                destroyed = true;
+            }
+        }
+    }
+    if destroyed {
+        let mut log = ecs.write_resource::<GameLog>();
+        log.log("The blast tears through the walls.".to_string());
+        invalidate_viewsheds(ecs);
+    }
+}
+
+/// Moves the target to a free tile, returning whether it went through
+fn teleport(ecs: &World, target: Entity, destination: Point) -> bool {
+    let mut map = ecs.write_resource::<GameMap>();
+    let idx = map.xy_idx(destination);
+    if map.blocked_tiles[idx] {
+        return false;
+    }
+    let mut positions = ecs.write_storage::<Position>();
+    let position = positions.get_mut(target).unwrap();
+    let old_idx = map.xy_idx(position.pos);
+    map.blocked_tiles[old_idx] = false;
+    map.blocked_tiles[idx] = true;
+    position.pos = destination;
+
+    if ecs.read_resource::<PlayerEntity>().entity == target {
+        ecs.write_resource::<PlayerPos>().pos = destination;
+    }
+    if let Some(viewshed) = ecs.write_storage::<Viewshed>().get_mut(target) {
+        viewshed.dirty = true;
+    }
+    true
+}
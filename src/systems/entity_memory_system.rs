@@ -0,0 +1,41 @@
+use specs::prelude::*;
+
+use crate::{
+    components::{ParticleLifetime, Renderable},
+    game_map::{GameMap, RememberedEntity},
+};
+
+pub struct EntityMemorySystem {}
+
+impl<'a> System<'a> for EntityMemorySystem {
+    type SystemData = (
+        WriteExpect<'a, GameMap>,
+        ReadStorage<'a, Renderable>,
+        ReadStorage<'a, ParticleLifetime>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut map, renderables, particles) = data;
+        let map = &mut *map;
+
+        // Remember what is on top of every tile in sight, like revealed tiles for terrain
+        for (idx, entities) in map.entities_tiles.iter().enumerate() {
+            if !map.visible_tiles[idx] {
+                continue;
+            }
+            let seen = entities
+                .iter()
+                .filter(|entity| !particles.contains(**entity))
+                .filter_map(|entity| renderables.get(*entity))
+                .min_by_key(|render| render.render_order)
+                .map(|render| RememberedEntity {
+                    glyph: render.glyph,
+                    fg: render.fg,
+                });
+            match seen {
+                Some(memory) => map.remembered_entities.insert(idx, memory),
+                None => map.remembered_entities.remove(&idx),
+            };
+        }
+    }
+}
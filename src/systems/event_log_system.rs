@@ -0,0 +1,61 @@
+use specs::{
+    prelude::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::*,
+    events::{register_reader, GameEvent},
+    gamelog::GameLog,
+    identification::ItemIdentification,
+};
+
+/// Narrates deaths, pickups and doors in the game log
+#[derive(Default)]
+pub struct EventLogSystem {
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'a> System<'a> for EventLogSystem {
+    type SystemData = (
+        Read<'a, EventChannel<GameEvent>>,
+        WriteExpect<'a, GameLog>,
+        ReadExpect<'a, ItemIdentification>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Name>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(register_reader(world));
+    }
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (events, mut log, identification, players, names) = data;
+        for event in events.read(self.reader.as_mut().unwrap()) {
+            match event {
+                GameEvent::EntityDied {
+                    name, was_player, ..
+                } => {
+                    if *was_player {
+                        log.log(format!("{} is down", name));
+                    } else {
+                        log.log(format!("{} is dead", name));
+                    }
+                }
+                GameEvent::ItemPickedUp { actor, item } if players.contains(*actor) => {
+                    if let Some(item_name) = names.get(*item) {
+                        log.log(format!(
+                            "You pick up the {}.",
+                            identification.display_name(&item_name.name)
+                        ));
+                    }
+                }
+                GameEvent::DoorOpened { actor } if players.contains(*actor) => {
+                    log.log("You open the door.".to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use bracket_lib::prelude::*;
+use specs::prelude::*;
+
+use crate::{
+    actions::{area_targets, item_effects},
+    components::*,
+    game_map::GameMap,
+    gamelog::GameLog,
+    identification::item_name,
+    map::Map,
+};
+
+use super::add_effect;
+
+/// Burns down the fuses of thrown grenades, setting them off when they run out.
+/// Run at the start of every round rather than from a dispatcher.
+pub fn burn_fuses(ecs: &mut World) {
+    let mut spent = Vec::new();
+    {
+        let entities = ecs.entities();
+        let mut fuses = ecs.write_storage::<LitFuse>();
+        let positions = ecs.read_storage::<Position>();
+        for (entity, fuse, pos) in (&entities, &mut fuses, &positions).join() {
+            fuse.turns -= 1;
+            if fuse.turns < 1 {
+                spent.push((entity, pos.pos));
+            }
+        }
+    }
+
+    for (grenade, pos) in spent {
+        detonate(ecs, grenade, pos);
+    }
+}
+
+fn detonate(ecs: &mut World, grenade: Entity, pos: Point) {
+    let targets = {
+        let map = ecs.read_resource::<GameMap>();
+        let radius = ecs
+            .read_storage::<AreaOfEffect>()
+            .get(grenade)
+            .map_or(0, |area_effect| area_effect.radius);
+        if map.visible_tiles[map.xy_idx(pos)] {
+            let name = item_name(ecs, grenade);
+            ecs.write_resource::<GameLog>()
+                .log(format!("The {} goes off.", name));
+        }
+        area_targets(&map, pos, radius)
+    };
+
+    for (effect, targets) in item_effects(ecs, grenade, grenade, Some(pos), targets) {
+        add_effect(ecs, effect, targets);
+    }
+    ecs.entities().delete(grenade).expect("Delete failed");
+}
@@ -0,0 +1,33 @@
+use specs::{
+    prelude::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    events::{register_reader, GameEvent},
+    resources::Noises,
+};
+
+/// Collects the noises made since the last monster turn for the AI to react to
+#[derive(Default)]
+pub struct HearingSystem {
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'a> System<'a> for HearingSystem {
+    type SystemData = (Read<'a, EventChannel<GameEvent>>, WriteExpect<'a, Noises>);
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(register_reader(world));
+    }
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (events, mut noises) = data;
+        for event in events.read(self.reader.as_mut().unwrap()) {
+            if let GameEvent::NoiseMade { pos, volume } = event {
+                noises.make(*pos, *volume);
+            }
+        }
+    }
+}
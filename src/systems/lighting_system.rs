@@ -0,0 +1,84 @@
+use bracket_lib::prelude::*;
+use specs::prelude::*;
+
+use crate::{
+    components::{Equipment, Facing, LightSource, Position, Viewshed},
+    game_map::GameMap,
+    map::{in_cone, Map},
+};
+
+pub struct LightingSystem {}
+
+impl<'a> System<'a> for LightingSystem {
+    type SystemData = (
+        WriteExpect<'a, GameMap>,
+        ReadStorage<'a, LightSource>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Equipment>,
+        ReadStorage<'a, Facing>,
+        WriteStorage<'a, Viewshed>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut map, light_sources, positions, equipments, facings, mut viewsheds) = data;
+        if map.ambient_light.len() != map.tiles.len() {
+            map.compute_ambient_light();
+        }
+
+        let mut light: Vec<RGB> = map
+            .ambient_light
+            .iter()
+            .map(|level| RGB::from_f32(*level, *level, *level))
+            .collect();
+        // Directional lights only shine when carried
+        for (source, pos) in (&light_sources, &positions).join() {
+            if source.cone.is_none() {
+                add_light(&map, &mut light, source, pos.pos, None);
+            }
+        }
+        for (equipment, pos, facing) in (&equipments, &positions, facings.maybe()).join() {
+            for item in equipment.slots.values() {
+                if let Some(source) = light_sources.get(*item) {
+                    add_light(&map, &mut light, source, pos.pos, facing);
+                }
+            }
+        }
+        for tile_light in light.iter_mut() {
+            tile_light.r = tile_light.r.min(1.0);
+            tile_light.g = tile_light.g.min(1.0);
+            tile_light.b = tile_light.b.min(1.0);
+        }
+
+        // What everyone can see depends on the light
+        if light != map.light {
+            map.light = light;
+            for viewshed in (&mut viewsheds).join() {
+                viewshed.dirty = true;
+            }
+        }
+    }
+}
+
+fn add_light(
+    map: &GameMap,
+    light: &mut [RGB],
+    source: &LightSource,
+    origin: Point,
+    facing: Option<&Facing>,
+) {
+    let lit_tiles = field_of_view(origin, source.radius, map);
+    for tile in lit_tiles.iter() {
+        if tile.x < 0 || tile.x >= map.width || tile.y < 0 || tile.y >= map.height {
+            continue;
+        }
+        if let (Some(half_angle), Some(facing)) = (source.cone, facing) {
+            if !in_cone(origin, facing.direction, *tile, half_angle) {
+                continue;
+            }
+        }
+        let distance = DistanceAlg::Pythagoras.distance2d(origin, *tile);
+        let intensity = 1.0 - distance / (source.radius as f32 + 1.0);
+        let idx = map.xy_idx(*tile);
+        light[idx] = light[idx] + source.color * intensity.max(0.0);
+    }
+}
@@ -1,6 +1,9 @@
 mod visibility_system;
 pub use visibility_system::*;
 
+mod lighting_system;
+pub use lighting_system::*;
+
 mod monster_ai_system;
 pub use monster_ai_system::*;
 
@@ -16,6 +19,33 @@ pub use saveload_system::*;
 mod points_of_interest_system;
 pub use points_of_interest_system::*;
 
+mod entity_memory_system;
+pub use entity_memory_system::*;
+
+mod status_system;
+pub use status_system::*;
+
+mod effect_system;
+pub use effect_system::*;
+
+mod event_log_system;
+pub use event_log_system::*;
+
+mod damage_effects_system;
+pub use damage_effects_system::*;
+
+mod stats_system;
+pub use stats_system::*;
+
+mod hearing_system;
+pub use hearing_system::*;
+
+mod fuse_system;
+pub use fuse_system::*;
+
+mod smoke_system;
+pub use smoke_system::*;
+
 pub mod particle_system;
 pub use particle_system::*;
 
@@ -26,8 +56,13 @@ pub fn with_gameplay_systems<'a, 'b>(
 ) -> DispatcherBuilder<'a, 'b> {
     dispatcher
         .with(ParticleSpawnSystem {}, "particle", &[])
-        .with(VisibilitySystem {}, "visibility", &[])
+        .with(LightingSystem {}, "lighting", &[])
+        .with(VisibilitySystem {}, "visibility", &["lighting"])
         .with(DeadCollection {}, "dead_collection", &[])
+        .with(EventLogSystem::default(), "event_log", &[])
+        .with(DamageEffectsSystem::default(), "damage_effects", &[])
+        .with(StatsSystem::default(), "stats", &[])
+        .with(HearingSystem::default(), "hearing", &[])
 }
 
 pub fn with_indexing_systems<'a, 'b>(
@@ -40,4 +75,5 @@ pub fn with_indexing_systems<'a, 'b>(
             "points_of_interest",
             &["map_indexing"],
         )
+        .with(EntityMemorySystem {}, "entity_memory", &["map_indexing"])
 }
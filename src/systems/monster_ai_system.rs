@@ -1,47 +1,149 @@
 use crate::actions::*;
+use crate::combat::average_roll;
 use crate::components::*;
+use crate::encumbrance::can_pick_up;
 use crate::game_map::GameMap;
 use crate::map::Map;
-use crate::resources::{PlayerEntity, PlayerPos};
+use crate::resources::{Movements, Noises};
+use crate::status::StatusKind;
+use crate::systems::{can_make_out, in_vision_cone, PERIPHERAL_ANGLE};
 use bracket_lib::prelude::*;
 use specs::prelude::*;
 
 type SystemData<'a> = (
     ReadExpect<'a, GameMap>,
-    ReadExpect<'a, PlayerPos>,
-    ReadExpect<'a, PlayerEntity>,
+    ReadExpect<'a, Noises>,
+    ReadExpect<'a, Movements>,
     ReadStorage<'a, Viewshed>,
+    ReadStorage<'a, Facing>,
     ReadStorage<'a, Monster>,
+    ReadStorage<'a, Player>,
     ReadStorage<'a, Position>,
-    WriteStorage<'a, Confused>,
+    ReadStorage<'a, Busy>,
+    ReadStorage<'a, StatusEffects>,
+    ReadStorage<'a, CombatStats>,
+    ReadStorage<'a, Inventory>,
+    ReadStorage<'a, Equipment>,
+    ReadStorage<'a, Item>,
+    ReadStorage<'a, Equippable>,
+    ReadStorage<'a, TwoHanded>,
+    ReadStorage<'a, MeleeWeapon>,
+    ReadStorage<'a, DefenseBonus>,
+    ReadStorage<'a, ProvidesHealing>,
+    ReadStorage<'a, LitFuse>,
+    WriteStorage<'a, Investigating>,
     Entities<'a>,
 );
 
 pub fn run_monster_ai(world: &mut World) {
+    take_turns(world, false);
+    // Hasted monsters get to act a second time
+    take_turns(world, true);
+    world.write_resource::<Noises>().clear();
+    world.write_resource::<Movements>().clear();
+}
+
+fn take_turns(world: &mut World, hasted_only: bool) {
     let mut actions: Vec<(Entity, AnyAction)> = Vec::new();
 
     {
         let (
-                map,
-                player_pos,
-                player_entity,
-                viewshed,
-                monster,
-                pos,
-                mut confused,
-                entities,
-            ): SystemData = world.system_data();
+            map,
+            noises,
+            movements,
+            viewshed,
+            facings,
+            monster,
+            players,
+            pos,
+            busy,
+            statuses,
+            combat_stats,
+            inventories,
+            equipments,
+            items,
+            equippables,
+            two_handed,
+            melee_weapons,
+            defense_bonuses,
+            healing,
+            lit_fuses,
+            mut investigating,
+            entities,
+        ): SystemData = world.system_data();
+
+        let operators: Vec<(Entity, Point)> = (&entities, &players, &pos)
+            .join()
+            .map(|(entity, _player, pos)| (entity, pos.pos))
+            .collect();
+        // Nobody picks up a live grenade
+        let ground_items: Vec<(Entity, Point)> = (&entities, &items, &pos, !&lit_fuses)
+            .join()
+            .map(|(entity, _item, pos, _)| (entity, pos.pos))
+            .collect();
 
         for (viewshed, monster_pos, _monster, entity) in
             (&viewshed, &pos, &monster, &entities).join()
         {
+            let status = statuses.get(entity);
+            if hasted_only && !status.is_some_and(|status| status.has(StatusKind::Hasted)) {
+                continue;
+            }
+            if status.is_some_and(|status| status.loses_turn()) {
+                continue;
+            }
+            if busy.contains(entity) {
+                actions.push((entity, Box::new(BusyAction {})));
+                continue;
+            }
+
+            // Look after themselves and their gear before anything else
+            let inventory = inventories.get(entity);
+            let gear_action = inventory.and_then(|inventory| {
+                let stats = combat_stats.get(entity)?;
+                heal_action(world, entity, stats, inventory, &healing).or_else(|| {
+                    let equipment = equipments.get(entity)?;
+                    equip_action(
+                        stats,
+                        inventory,
+                        equipment,
+                        &equippables,
+                        &two_handed,
+                        &melee_weapons,
+                        &defense_bonuses,
+                    )
+                })
+            });
+            if let Some(action) = gear_action {
+                actions.push((entity, action));
+                continue;
+            }
+            // Fighting comes first when an operator is within reach
+            let in_melee = operators.iter().any(|(_operator, pos)| {
+                viewshed.visible_tiles.contains(pos)
+                    && DistanceAlg::Pythagoras.distance2d(monster_pos.pos, *pos) < 1.5
+            });
+            let underfoot = ground_items
+                .iter()
+                .find(|(_item, pos)| !in_melee && *pos == monster_pos.pos);
+            if let (Some(_inventory), Some((item, _pos))) = (inventory, underfoot) {
+                // Items that would overload the monster are left lying there
+                if can_pick_up(world, entity, *item) {
+                    actions.push((entity, Box::new(PickItemAction { target: *item })));
+                    continue;
+                }
+            }
+
             let action = get_monster_action(
-                &mut confused,
+                &mut investigating,
                 entity,
                 viewshed,
-                player_pos.pos,
+                facings.get(entity),
+                status,
                 monster_pos.pos,
-                player_entity.entity,
+                &operators,
+                &noises,
+                &movements,
                 &map,
             );
             if let Some(action) = action {
@@ -55,49 +157,176 @@ pub fn run_monster_ai(world: &mut World) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn get_monster_action(
-    confused: &mut WriteStorage<Confused>,
+    investigating: &mut WriteStorage<Investigating>,
     entity: Entity,
     viewshed: &Viewshed,
-    player_pos: Point,
+    facing: Option<&Facing>,
+    status: Option<&StatusEffects>,
     monster_pos: Point,
-    player_entity: Entity,
+    operators: &[(Entity, Point)],
+    noises: &Noises,
+    movements: &Movements,
     map: &GameMap,
 ) -> Option<AnyAction> {
-    let mut can_act = true;
-    let is_confused = confused.get_mut(entity);
-    if let Some(i_am_confused) = is_confused {
-        i_am_confused.turns -= 1;
-        if i_am_confused.turns < 1 {
-            confused.remove(entity);
-        }
-        can_act = false;
-    }
-    if !viewshed.visible_tiles.contains(&player_pos) {
-        can_act = false;
-    }
-    if can_act {
-        let distance = DistanceAlg::Pythagoras.distance2d(monster_pos, player_pos);
+    // Go after the closest operator in sight
+    let target = operators
+        .iter()
+        .filter(|(_operator, pos)| viewshed.visible_tiles.contains(pos))
+        .map(|(operator, pos)| {
+            let distance = DistanceAlg::Pythagoras.distance2d(monster_pos, *pos);
+            (*operator, *pos, distance)
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2));
+
+    if let Some((target_entity, target_pos, distance)) = target {
+        investigating.remove(entity);
         if distance < 1.5 {
             let action = MeleeAction {
-                target: player_entity,
+                target: target_entity,
             };
             return Some(Box::new(action));
         }
-        let path = a_star_search(
-            map.xy_idx(monster_pos) as i32,
-            map.xy_idx(player_pos) as i32,
-            &*map,
-        );
-        if path.success && path.steps.len() > 1 {
-            let target = Point {
-                x: path.steps[1] as i32 % map.width,
-                y: path.steps[1] as i32 / map.width,
-            };
-            let action = MoveAction { target };
+        return step_towards(map, monster_pos, target_pos);
+    }
+
+    // Nobody in sight: check out movement caught out of the corner of the eye,
+    // or else the closest noise within earshot
+    let blinded = status.is_some_and(|status| status.has(StatusKind::Blinded));
+    let glimpsed = movements
+        .iter()
+        .filter(|_pos| !blinded)
+        .map(|pos| (*pos, DistanceAlg::Pythagoras.distance2d(monster_pos, *pos)))
+        .filter(|(pos, distance)| {
+            *distance <= viewshed.range as f32
+                && in_vision_cone(viewshed.cone, facing, monster_pos, *pos, PERIPHERAL_ANGLE)
+                && can_make_out(map, monster_pos, *pos, viewshed.night_vision, false)
+                && in_line_of_sight(map, monster_pos, *pos)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(pos, _distance)| pos);
+    let heard = || {
+        noises
+            .iter()
+            .map(|noise| {
+                let distance = DistanceAlg::Pythagoras.distance2d(monster_pos, noise.pos);
+                (noise, distance)
+            })
+            .filter(|(noise, distance)| *distance <= noise.volume as f32)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(noise, _distance)| noise.pos)
+    };
+    if let Some(pos) = glimpsed.or_else(heard) {
+        let already_investigating = investigating.get(entity).map(|i| i.pos) == Some(pos);
+        investigating
+            .insert(entity, Investigating { pos })
+            .expect("Unable to insert investigating");
+        // Turn to look before going to check it out
+        if !already_investigating {
+            let action = TurnAction { target: pos };
             return Some(Box::new(action));
         }
     }
 
+    if let Some(destination) = investigating.get(entity).map(|i| i.pos) {
+        let action = step_towards(map, monster_pos, destination);
+        if action.is_none() {
+            investigating.remove(entity);
+        }
+        return action;
+    }
+
+    None
+}
+
+/// Whether nothing blocks the sight between the two tiles
+fn in_line_of_sight(map: &GameMap, from: Point, to: Point) -> bool {
+    line2d(LineAlg::Bresenham, from, to)
+        .into_iter()
+        .filter(|tile| *tile != from && *tile != to)
+        .all(|tile| !map.is_opaque(map.xy_idx(tile)))
+}
+
+/// Drinks the best healing item carried once badly hurt
+fn heal_action(
+    world: &World,
+    entity: Entity,
+    stats: &CombatStats,
+    inventory: &Inventory,
+    healing: &ReadStorage<ProvidesHealing>,
+) -> Option<AnyAction> {
+    if stats.hp > stats.max_hp / 2 {
+        return None;
+    }
+    let item = inventory
+        .items
+        .iter()
+        .filter(|item| use_refusal(world, entity, **item).is_none())
+        .filter_map(|item| Some((*item, healing.get(*item)?.heal_amount)))
+        .max_by_key(|(_item, amount)| *amount)?
+        .0;
+    Some(Box::new(UseItemAction { item, target: None }))
+}
+
+/// Swaps in a carried weapon or piece of armour better than the one worn
+fn equip_action(
+    stats: &CombatStats,
+    inventory: &Inventory,
+    equipment: &Equipment,
+    equippables: &ReadStorage<Equippable>,
+    two_handed: &ReadStorage<TwoHanded>,
+    melee_weapons: &ReadStorage<MeleeWeapon>,
+    defense_bonuses: &ReadStorage<DefenseBonus>,
+) -> Option<AnyAction> {
+    let gear_score = |item: Entity| {
+        let mut score = None;
+        if let Some(weapon) = melee_weapons.get(item) {
+            score = Some(average_roll(weapon.damage) + weapon.hit_bonus as f32);
+        }
+        if let Some(bonus) = defense_bonuses.get(item) {
+            score = Some(score.unwrap_or(0.0) + (bonus.dodge + bonus.armor) as f32);
+        }
+        score
+    };
+
+    let target = inventory.items.iter().copied().find(|item| {
+        let (Some(equippable), Some(score)) = (equippables.get(*item), gear_score(*item)) else {
+            return false;
+        };
+        let worn_score = |slot: EquipmentSlot| match equipment.slots.get(&slot) {
+            Some(worn) => gear_score(*worn).unwrap_or(0.0),
+            None if slot == EquipmentSlot::Melee => average_roll(stats.damage),
+            None => 0.0,
+        };
+        let wields_two_handed = equipment
+            .slots
+            .get(&EquipmentSlot::Melee)
+            .is_some_and(|weapon| two_handed.contains(*weapon));
+        match equippable.slot {
+            EquipmentSlot::Shield if wields_two_handed => false,
+            EquipmentSlot::Melee if two_handed.contains(*item) => {
+                score > worn_score(EquipmentSlot::Melee) + worn_score(EquipmentSlot::Shield)
+            }
+            slot => score > worn_score(slot),
+        }
+    })?;
+    Some(Box::new(EquipItemAction { target }))
+}
+
+fn step_towards(map: &GameMap, from: Point, to: Point) -> Option<AnyAction> {
+    let path = a_star_search(map.xy_idx(from) as i32, map.xy_idx(to) as i32, map);
+    if path.success && path.steps.len() > 1 {
+        let target = Point {
+            x: path.steps[1] as i32 % map.width,
+            y: path.steps[1] as i32 / map.width,
+        };
+        if map.is_door_closed(path.steps[1]) {
+            let action = OpenDoorAction { target };
+            return Some(Box::new(action));
+        }
+        let action = MoveAction { target };
+        return Some(Box::new(action));
+    }
     None
 }
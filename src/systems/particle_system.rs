@@ -18,6 +18,9 @@ impl<'a> System<'a> for ParticleSpawnSystem {
     fn run(&mut self, data: Self::SystemData) {
         let (entities, mut positions, mut renderables, mut particles, mut particle_builder) = data;
         for new_particle in particle_builder.requests.iter() {
+            if new_particle.delay > 0.0 {
+                continue;
+            }
             let p = entities.create();
             positions
                 .insert(
@@ -48,7 +51,9 @@ impl<'a> System<'a> for ParticleSpawnSystem {
                 .expect("Unable to insert lifetime");
         }
 
-        particle_builder.requests.clear();
+        particle_builder
+            .requests
+            .retain(|request| request.delay > 0.0);
     }
 }
 
@@ -58,7 +63,7 @@ pub fn cull_dead_particles(ecs: &mut World, ctx: &BTerm) {
         // Age out particles
         let mut particles = ecs.write_storage::<ParticleLifetime>();
         let entities = ecs.entities();
-        for (entity, mut particle) in (&entities, &mut particles).join() {
+        for (entity, particle) in (&entities, &mut particles).join() {
             particle.lifetime_ms -= ctx.frame_time_ms;
             if particle.lifetime_ms < 0.0 {
                 dead_particles.push(entity);
@@ -68,6 +73,12 @@ pub fn cull_dead_particles(ecs: &mut World, ctx: &BTerm) {
     for dead in dead_particles.iter() {
         ecs.delete_entity(*dead).expect("Particle will not die");
     }
+
+    // Bring delayed particles closer to being spawned
+    let mut particle_builder = ecs.write_resource::<ParticleBuilder>();
+    for request in particle_builder.requests.iter_mut() {
+        request.delay -= ctx.frame_time_ms;
+    }
 }
 
 struct ParticleRequest {
@@ -76,6 +87,7 @@ struct ParticleRequest {
     bg: RGB,
     glyph: FontCharType,
     lifetime: f32,
+    delay: f32,
 }
 
 pub struct ParticleBuilder {
@@ -91,12 +103,26 @@ impl ParticleBuilder {
     }
 
     pub fn request(&mut self, pos: Point, fg: RGB, bg: RGB, glyph: FontCharType, lifetime: f32) {
+        self.request_delayed(pos, fg, bg, glyph, lifetime, 0.0);
+    }
+
+    /// Spawns the particle only once `delay` milliseconds have passed, e.g. to animate a trail
+    pub fn request_delayed(
+        &mut self,
+        pos: Point,
+        fg: RGB,
+        bg: RGB,
+        glyph: FontCharType,
+        lifetime: f32,
+        delay: f32,
+    ) {
         self.requests.push(ParticleRequest {
             pos,
             fg,
             bg,
             glyph,
             lifetime,
+            delay,
         });
     }
 }
@@ -19,11 +19,11 @@ impl<'a> System<'a> for PointsOfInterestSystem {
 
     fn run(&mut self, data: Self::SystemData) {
         let (map, mut poi, viewshed, players) = data;
+        poi.clear();
         for (_player, viewshed) in (&players, &viewshed).join() {
-            poi.clear();
             for vis in viewshed.visible_tiles.iter() {
                 let idx = map.xy_idx(*vis);
-                if !map.entities_tiles[idx].is_empty() {
+                if !map.entities_tiles[idx].is_empty() && !poi.contains(*vis) {
                     poi.add(*vis)
                 }
             }
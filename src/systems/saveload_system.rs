@@ -9,10 +9,11 @@ use specs::{error::NoError, saveload::*, *};
 use crate::{
     components::*,
     game_map::GameMap,
-    resources::{PlayerEntity, PlayerPos},
+    identification::ItemIdentification,
+    resources::{GameStats, PlayerEntity, PlayerPos},
 };
 
-const SAVE_PATH: &'static str = "./savegame.json";
+const SAVE_PATH: &str = "./savegame.json";
 
 macro_rules! serialize_individually {
     ($ecs:expr, $ser:expr, $data:expr, $( $type:ty),*) => {
@@ -32,9 +33,17 @@ macro_rules! serialize_individually {
 pub fn save_game(ecs: &mut World) {
     // Create helper
     let mapcopy = ecs.get_mut::<GameMap>().unwrap().clone();
+    let stats = *ecs.read_resource::<GameStats>();
+    let identification = (*ecs.read_resource::<ItemIdentification>()).clone();
+    let active_operator = ecs.read_resource::<PlayerEntity>().entity;
     let savehelper = ecs
         .create_entity()
-        .with(SerializationHelper { map: mapcopy })
+        .with(SerializationHelper {
+            map: mapcopy,
+            stats,
+            identification,
+            active_operator,
+        })
         .marked::<SimpleMarker<SerializeMe>>()
         .build();
 
@@ -60,15 +69,39 @@ pub fn save_game(ecs: &mut World) {
             BlocksTile,
             CombatStats,
             Item,
+            Corpse,
             Equipment,
             Inventory,
             Consumable,
+            Quantity,
+            Charges,
+            Recharges,
+            Weight,
             Equippable,
+            TwoHanded,
             Ranged,
             InflictsDamage,
             AreaOfEffect,
             ProvidesHealing,
-            Confusion,
+            InflictsStatus,
+            Smoke,
+            Grenade,
+            LitFuse,
+            Shatters,
+            StatusEffects,
+            SpawnsEntity,
+            Teleports,
+            Identifies,
+            Busy,
+            Vaulting,
+            Investigating,
+            LightSource,
+            Facing,
+            NightVision,
+            Firearm,
+            Ammunition,
+            MeleeWeapon,
+            DefenseBonus,
             SerializationHelper
         );
     }
@@ -132,15 +165,39 @@ pub fn load_game(ecs: &mut World) {
             BlocksTile,
             CombatStats,
             Item,
+            Corpse,
             Equipment,
             Inventory,
             Consumable,
+            Quantity,
+            Charges,
+            Recharges,
+            Weight,
             Equippable,
+            TwoHanded,
             Ranged,
             InflictsDamage,
             AreaOfEffect,
             ProvidesHealing,
-            Confusion,
+            InflictsStatus,
+            Smoke,
+            Grenade,
+            LitFuse,
+            Shatters,
+            StatusEffects,
+            SpawnsEntity,
+            Teleports,
+            Identifies,
+            Busy,
+            Vaulting,
+            Investigating,
+            LightSource,
+            Facing,
+            NightVision,
+            Firearm,
+            Ammunition,
+            MeleeWeapon,
+            DefenseBonus,
             SerializationHelper
         );
     }
@@ -149,20 +206,20 @@ pub fn load_game(ecs: &mut World) {
     {
         let entities = ecs.entities();
         let helper = ecs.read_storage::<SerializationHelper>();
-        let player = ecs.read_storage::<Player>();
         let position = ecs.read_storage::<Position>();
         for (e, h) in (&entities, &helper).join() {
             let mut worldmap = ecs.write_resource::<GameMap>();
             *worldmap = h.map.clone();
             worldmap.entities_tiles = vec![Vec::new(); (worldmap.width * worldmap.height) as usize];
+            *ecs.write_resource::<GameStats>() = h.stats;
+            *ecs.write_resource::<ItemIdentification>() = h.identification.clone();
+            // Resume with the operator who was active when saving
+            ecs.write_resource::<PlayerEntity>().entity = h.active_operator;
+            if let Some(pos) = position.get(h.active_operator) {
+                ecs.write_resource::<PlayerPos>().pos = pos.pos;
+            }
             deleteme = Some(e);
         }
-        for (e, _p, pos) in (&entities, &player, &position).join() {
-            let mut ppos = ecs.write_resource::<PlayerPos>();
-            ppos.pos = pos.pos.clone();
-            let mut player_resource = ecs.write_resource::<PlayerEntity>();
-            player_resource.entity = e.clone();
-        }
     }
     ecs.delete_entity(deleteme.unwrap())
         .expect("Unable to delete helper");
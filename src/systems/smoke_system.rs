@@ -0,0 +1,38 @@
+use specs::prelude::*;
+
+use crate::{components::*, game_map::GameMap, map::Map};
+
+/// Thins smoke clouds out, clearing the line of sight once they are gone.
+/// Run at the end of every round rather than from a dispatcher.
+pub struct SmokeSystem {}
+
+impl<'a> System<'a> for SmokeSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteExpect<'a, GameMap>,
+        WriteStorage<'a, Smoke>,
+        WriteStorage<'a, Viewshed>,
+        ReadStorage<'a, Position>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, mut map, mut smokes, mut viewsheds, positions) = data;
+
+        let mut cleared = false;
+        for (entity, smoke, pos) in (&entities, &mut smokes, &positions).join() {
+            smoke.turns -= 1;
+            if smoke.turns < 1 {
+                let idx = map.xy_idx(pos.pos);
+                map.smoke_tiles.remove(&idx);
+                entities.delete(entity).expect("Unable to delete");
+                cleared = true;
+            }
+        }
+
+        if cleared {
+            for viewshed in (&mut viewsheds).join() {
+                viewshed.dirty = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use specs::{
+    prelude::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::Player,
+    events::{register_reader, GameEvent},
+    resources::GameStats,
+};
+
+#[derive(Default)]
+pub struct StatsSystem {
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'a> System<'a> for StatsSystem {
+    type SystemData = (
+        Read<'a, EventChannel<GameEvent>>,
+        Write<'a, GameStats>,
+        ReadStorage<'a, Player>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(register_reader(world));
+    }
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (events, mut stats, players) = data;
+        for event in events.read(self.reader.as_mut().unwrap()) {
+            match event {
+                GameEvent::EntityDamaged { target, amount, .. } => {
+                    if players.contains(*target) {
+                        stats.damage_taken += amount;
+                    } else {
+                        stats.damage_dealt += amount;
+                    }
+                }
+                GameEvent::EntityDied { was_player, .. } => {
+                    if *was_player {
+                        stats.operators_lost += 1;
+                    } else {
+                        stats.kills += 1;
+                    }
+                }
+                GameEvent::ItemPickedUp { actor, .. } if players.contains(*actor) => {
+                    stats.items_picked_up += 1;
+                }
+                GameEvent::DoorOpened { actor } if players.contains(*actor) => {
+                    stats.doors_opened += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+}
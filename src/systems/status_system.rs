@@ -0,0 +1,95 @@
+use specs::{prelude::*, shrev::EventChannel};
+
+use crate::{components::*, events::GameEvent, gamelog::GameLog, status::StatusKind};
+
+/// Ticks status effects down once per turn, dealing damage over time and expiring them.
+/// Run at the end of every round rather than from a dispatcher.
+pub struct StatusSystem {}
+
+impl<'a> System<'a> for StatusSystem {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        Write<'a, EventChannel<GameEvent>>,
+        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, StatusEffects>,
+        WriteStorage<'a, CombatStats>,
+        WriteStorage<'a, Viewshed>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Player>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut events,
+            mut log,
+            mut statuses,
+            mut combat_stats,
+            mut viewsheds,
+            positions,
+            names,
+            players,
+        ) = data;
+
+        let mut recovered = Vec::new();
+        for (entity, status, stats, pos, name) in (
+            &entities,
+            &mut statuses,
+            &mut combat_stats,
+            &positions,
+            &names,
+        )
+            .join()
+        {
+            let is_player = players.contains(entity);
+            for effect in status.effects.iter_mut() {
+                match effect.kind {
+                    StatusKind::Bleeding | StatusKind::Poisoned => {
+                        stats.deal_damage(effect.potency);
+                        events.single_write(GameEvent::EntityDamaged {
+                            target: entity,
+                            pos: pos.pos,
+                            amount: effect.potency,
+                            bleeds: effect.kind == StatusKind::Bleeding,
+                        });
+                        if is_player {
+                            log.log(format!(
+                                "{} loses {} hp from {}.",
+                                name.name,
+                                effect.potency,
+                                effect.kind.label()
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+                effect.turns -= 1;
+            }
+
+            for effect in status.effects.iter().filter(|effect| effect.turns < 1) {
+                if is_player {
+                    log.log(format!(
+                        "{} is no longer {}.",
+                        name.name,
+                        effect.kind.label()
+                    ));
+                }
+                if effect.kind == StatusKind::Blinded {
+                    if let Some(viewshed) = viewsheds.get_mut(entity) {
+                        viewshed.dirty = true;
+                    }
+                }
+            }
+            status.effects.retain(|effect| effect.turns > 0);
+            if status.effects.is_empty() {
+                recovered.push(entity);
+            }
+        }
+
+        for entity in recovered {
+            statuses.remove(entity);
+        }
+    }
+}
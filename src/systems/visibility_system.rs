@@ -2,11 +2,47 @@ use bracket_lib::prelude::*;
 use specs::prelude::*;
 
 use crate::{
-    components::{Player, Position, Viewshed},
+    components::{Equipment, Facing, NightVision, Player, Position, StatusEffects, Viewshed},
     game_map::GameMap,
-    map::Map,
+    map::{in_cone, Map},
+    status::StatusKind,
 };
 
+/// Degrees on each side of a vision cone where movement still catches the eye
+pub const PERIPHERAL_ANGLE: f32 = 30.0;
+
+/// Whether a tile falls within a vision cone widened by the given angle on each side
+pub fn in_vision_cone(
+    cone: Option<f32>,
+    facing: Option<&Facing>,
+    origin: Point,
+    tile: Point,
+    widening: f32,
+) -> bool {
+    match (cone, facing) {
+        (Some(half_angle), Some(facing)) => {
+            in_cone(origin, facing.direction, tile, half_angle + widening)
+        }
+        _ => true,
+    }
+}
+
+/// Whether blindness and darkness still let the viewer make out a tile
+pub fn can_make_out(
+    map: &GameMap,
+    origin: Point,
+    tile: Point,
+    night_vision: bool,
+    blinded: bool,
+) -> bool {
+    let adjacent = DistanceAlg::Chebyshev.distance2d(origin, tile) <= 1.0;
+    if blinded {
+        return adjacent;
+    }
+    // Darkness hides everything but the immediate surroundings
+    night_vision || adjacent || map.is_lit(map.xy_idx(tile))
+}
+
 pub struct VisibilitySystem {}
 
 impl<'a> System<'a> for VisibilitySystem {
@@ -15,31 +51,75 @@ impl<'a> System<'a> for VisibilitySystem {
         WriteStorage<'a, Viewshed>,
         WriteStorage<'a, Position>,
         ReadStorage<'a, Player>,
+        ReadStorage<'a, Equipment>,
+        ReadStorage<'a, NightVision>,
+        ReadStorage<'a, Facing>,
+        ReadStorage<'a, StatusEffects>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut map, mut viewshed, pos, players) = data;
-        for (player, viewshed, pos) in (players.maybe(), &mut viewshed, &pos).join() {
+        let (mut map, mut viewshed, pos, players, equipments, night_visions, facings, statuses) =
+            data;
+        let mut squad_vision_changed = false;
+        for (player, viewshed, pos, equipment, facing, status) in (
+            players.maybe(),
+            &mut viewshed,
+            &pos,
+            equipments.maybe(),
+            facings.maybe(),
+            statuses.maybe(),
+        )
+            .join()
+        {
+            let night_vision = equipment.is_some_and(|equipment| {
+                equipment
+                    .slots
+                    .values()
+                    .any(|item| night_visions.contains(*item))
+            });
+            if night_vision != viewshed.night_vision {
+                viewshed.night_vision = night_vision;
+                viewshed.dirty = true;
+            }
+
             if viewshed.dirty {
                 viewshed.visible_tiles.clear();
                 viewshed.visible_tiles = field_of_view(pos.pos, viewshed.range, &*map);
                 viewshed
                     .visible_tiles
                     .retain(|p| p.x >= 0 && p.x < map.width && p.y >= 0 && p.y < map.height);
+                let cone = viewshed.cone;
+                viewshed
+                    .visible_tiles
+                    .retain(|p| in_vision_cone(cone, facing, pos.pos, *p, 0.0));
+                let blinded = status.is_some_and(|status| status.has(StatusKind::Blinded));
+                viewshed
+                    .visible_tiles
+                    .retain(|p| can_make_out(&map, pos.pos, *p, night_vision, blinded));
 
-                // If this is the player, reveal what they can see
-                if let Some(_p) = player {
-                    for t in map.visible_tiles.iter_mut() {
-                        *t = false
-                    }
-                    for vis in viewshed.visible_tiles.iter() {
-                        let idx = map.xy_idx(*vis);
-                        map.revealed_tiles[idx] = true;
-                        map.visible_tiles[idx] = true;
-                    }
+                if player.is_some() {
+                    squad_vision_changed = true;
                 }
                 viewshed.dirty = false;
             }
         }
+
+        // The squad shares its vision: reveal what any operator can see
+        if squad_vision_changed {
+            for t in map.visible_tiles.iter_mut() {
+                *t = false
+            }
+            map.night_vision_tiles.clear();
+            for (_player, viewshed) in (&players, &viewshed).join() {
+                for vis in viewshed.visible_tiles.iter() {
+                    let idx = map.xy_idx(*vis);
+                    map.revealed_tiles[idx] = true;
+                    map.visible_tiles[idx] = true;
+                    if viewshed.night_vision && !map.is_lit(idx) {
+                        map.night_vision_tiles.insert(idx);
+                    }
+                }
+            }
+        }
     }
 }
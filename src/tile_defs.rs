@@ -0,0 +1,81 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use bracket_lib::prelude::RGB;
+use serde::{Deserialize, Serialize};
+
+use crate::game_map::TileType;
+
+const TILE_DEFS_JSON: &str = include_str!("../resources/tiles.json");
+
+static TILE_DEFS: OnceLock<HashMap<TileType, TileDef>> = OnceLock::new();
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Serialize, Deserialize)]
+pub enum Cover {
+    None,
+    Half,
+    Full,
+}
+
+impl Cover {
+    pub fn label(self) -> &'static str {
+        match self {
+            Cover::None => "no cover",
+            Cover::Half => "half cover",
+            Cover::Full => "full cover",
+        }
+    }
+
+    /// Points removed from the chance to hit, in percent
+    pub fn hit_penalty(self) -> i32 {
+        match self {
+            Cover::None => 0,
+            Cover::Half => 20,
+            Cover::Full => 40,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TileDef {
+    pub name: String,
+    pub opaque: bool,
+    pub walkable: bool,
+    pub movement_cost: f32,
+    pub glyph: char,
+    fg: [f32; 3],
+    bg: Option<[f32; 3]>,
+    pub cover: Cover,
+    pub flammable: bool,
+    /// Damage the tile can take before being destroyed. Indestructible if absent.
+    pub hit_points: Option<i32>,
+    pub destroyed_into: Option<TileType>,
+    /// Decal colour left behind when the tile is destroyed
+    debris: Option<[f32; 3]>,
+}
+
+impl TileDef {
+    pub fn fg(&self) -> RGB {
+        to_rgb(self.fg)
+    }
+
+    pub fn bg(&self) -> Option<RGB> {
+        self.bg.map(to_rgb)
+    }
+
+    pub fn debris(&self) -> Option<RGB> {
+        self.debris.map(to_rgb)
+    }
+}
+
+fn to_rgb(color: [f32; 3]) -> RGB {
+    RGB::from_f32(color[0], color[1], color[2])
+}
+
+pub fn tile_def(tile: TileType) -> &'static TileDef {
+    TILE_DEFS
+        .get_or_init(|| {
+            serde_json::from_str(TILE_DEFS_JSON).expect("Unable to parse tile definitions")
+        })
+        .get(&tile)
+        .expect("Missing tile definition")
+}